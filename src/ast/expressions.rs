@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fmt::Display, hash::Hash};
+use std::{fmt::Display, hash::Hash};
 
 use super::statements::BlockStatement;
 use crate::lexer::{keywords, token};
@@ -19,6 +19,9 @@ pub enum AllExpressions {
     IndexExpression(IndexExpression),
     RangeExpression(RangeExpression),
     HashLiteral(HashLiteral),
+    Spread(SpreadExpression),
+    Block(BlockExpression),
+    Match(MatchExpression),
     NullLiteral,
 }
 
@@ -40,6 +43,9 @@ impl Display for AllExpressions {
             AllExpressions::Assignment(v) => v.to_string(),
             AllExpressions::RangeExpression(v) => v.to_string(),
             AllExpressions::HashLiteral(v) => v.to_string(),
+            AllExpressions::Spread(v) => v.to_string(),
+            AllExpressions::Block(v) => v.to_string(),
+            AllExpressions::Match(v) => v.to_string(),
         };
         write!(f, "{}", out)
     }
@@ -76,7 +82,7 @@ pub struct StringLiteral {
 
 impl Display for StringLiteral {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.token.literal)
+        write!(f, "\"{}\"", self.token.literal)
     }
 }
 
@@ -157,19 +163,31 @@ pub struct IfExpression {
 
 impl Display for IfExpression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut consequence = String::new();
-        for line in self.consequence.to_string().lines() {
-            consequence.push_str(format!("  {};\n", line).as_str());
+        let mut out = format!(
+            "if {} {}",
+            parenthesized(&self.condition),
+            self.consequence.to_braced_string()
+        );
+
+        if let Some(v) = &self.alternative {
+            out.push_str(format!(" else {}", v.to_braced_string()).as_str());
         }
 
-        let mut out = format!("if {} {{ \n{}}}", self.condition, consequence);
+        write!(f, "{}", out)
+    }
+}
 
-        match &self.alternative {
-            Some(v) => out.push_str(format!("else {}", v).as_str()),
-            None => {}
-        };
+/// A standalone block such as `{ let t = 1; t + 1 }`, which evaluates to the value of its last
+/// statement
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct BlockExpression {
+    pub token: token::Token,
+    pub body: BlockStatement,
+}
 
-        write!(f, "{}", out)
+impl Display for BlockExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.body.to_braced_string())
     }
 }
 
@@ -184,7 +202,15 @@ impl Display for FunctionLiteral {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let params: Vec<String> = self.parameters.iter().map(|v| v.to_string()).collect();
         let mut out = String::new();
-        out.push_str(format!("{}({}){}", self.token.literal, params.join(","), self.body).as_str());
+        out.push_str(
+            format!(
+                "{}({}) {}",
+                self.token.literal,
+                params.join(", "),
+                self.body.to_braced_string()
+            )
+            .as_str(),
+        );
 
         write!(f, "{}", out)
     }
@@ -239,11 +265,23 @@ pub struct IndexExpression {
     pub token: token::Token,
     pub left: Box<AllExpressions>,
     pub index: Box<AllExpressions>,
+    /// an optional access, written as `left?[index]`, evaluates to null when the left side is null
+    pub optional: bool,
+}
+
+/// Returns the opening bracket of an index or a range expression
+fn index_bracket(optional: bool) -> &'static str {
+    if optional {
+        "?["
+    } else {
+        "["
+    }
 }
 
 impl Display for IndexExpression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let out = format!("({}[{}])", self.left, self.index);
+        let bracket = index_bracket(self.optional);
+        let out = format!("({}{}{}])", self.left, bracket, self.index);
         write!(f, "{}", out)
     }
 }
@@ -254,11 +292,16 @@ pub struct RangeExpression {
     pub left: Box<AllExpressions>,
     pub left_index: Box<AllExpressions>,
     pub right_index: Box<AllExpressions>,
+    pub optional: bool,
 }
 
 impl Display for RangeExpression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let out = format!("({}[{}:{}])", self.left, self.left_index, self.right_index);
+        let bracket = index_bracket(self.optional);
+        let out = format!(
+            "({}{}{}:{}])",
+            self.left, bracket, self.left_index, self.right_index
+        );
         write!(f, "{}", out)
     }
 }
@@ -266,7 +309,7 @@ impl Display for RangeExpression {
 #[derive(Clone)]
 pub struct HashLiteral {
     pub token: token::Token,
-    pub pairs: HashMap<AllExpressions, AllExpressions>,
+    pub pairs: Vec<(AllExpressions, AllExpressions)>, // in the order of the source
 }
 
 impl PartialEq for HashLiteral {
@@ -288,10 +331,95 @@ impl Display for HashLiteral {
         let pairs = self
             .pairs
             .iter()
-            .map(|(key, item)| format!("{}:{}", key, item))
+            .map(|(key, item)| format!("{}: {}", key, item))
             .collect::<Vec<String>>()
             .join(", ");
 
         write!(f, "{{{}}}", pairs)
     }
 }
+
+/// Expands the elements of an array in place. It's only allowed as a call argument or an array element,
+/// e.g. `add(...args)` or `[0, ...xs]`.
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct SpreadExpression {
+    pub token: token::Token, // Spread token
+    pub value: Box<AllExpressions>,
+}
+
+impl Display for SpreadExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "...{}", self.value)
+    }
+}
+
+/// Evaluates the body of the first arm whose pattern matches the value, as in
+/// `match pair { [a, b] => a + b, n => n }`. The names bound by the pattern are only visible in the arm.
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct MatchExpression {
+    pub token: token::Token, // Match token
+    pub value: Box<AllExpressions>,
+    pub arms: Vec<MatchArm>,
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct MatchArm {
+    pub pattern: MatchPattern,
+    pub body: Box<AllExpressions>,
+}
+
+/// The pattern of a match arm
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub enum MatchPattern {
+    /// matches any value and binds it to the name
+    Binding(Identifier),
+    /// matches an array with exactly as many elements as names, binding each name to the element at
+    /// the same position
+    Array(Vec<Identifier>),
+    /// matches a hash map which has a string key for each name, binding each name to its value
+    HashMap(Vec<Identifier>),
+    /// matches a value equal to the result of the expression, such as `0` or `"a"`
+    Value(Box<AllExpressions>),
+}
+
+impl Display for MatchPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |names: &[Identifier]| {
+            names
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match self {
+            MatchPattern::Binding(name) => write!(f, "{}", name),
+            MatchPattern::Array(names) => write!(f, "[{}]", join(names)),
+            MatchPattern::HashMap(names) => write!(f, "{{{}}}", join(names)),
+            MatchPattern::Value(expr) => write!(f, "{}", expr),
+        }
+    }
+}
+
+impl Display for MatchExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let arms = self
+            .arms
+            .iter()
+            .map(|arm| format!("{} => {}", arm.pattern, arm.body))
+            .collect::<Vec<String>>()
+            .join(", ");
+        write!(f, "match {} {{ {} }}", self.value, arms)
+    }
+}
+
+/// Returns the expression wrapped in parentheses, unless its string form is already parenthesized.
+///
+/// Used for rendering the conditions of `if` expressions and `while` statements.
+pub fn parenthesized(expr: &AllExpressions) -> String {
+    match expr {
+        AllExpressions::InfixExpression(_) | AllExpressions::PrefixExpression(_) => {
+            expr.to_string()
+        }
+        _ => format!("({})", expr),
+    }
+}
@@ -18,7 +18,7 @@ mod tests {
     use super::{
         expressions::{AllExpressions, Identifier},
         program::Program,
-        statements::{AllStatements, LetStatement},
+        statements::{AllStatements, LetStatement, LetTarget},
     };
     use crate::lexer::{
         keywords,
@@ -41,7 +41,7 @@ mod tests {
 
         let stmt = LetStatement {
             token: new_token(TokenType::Let, keywords::LET),
-            name,
+            target: LetTarget::Identifier(name),
             value: Box::new(AllExpressions::Identifier(value)),
         };
 
@@ -2,6 +2,7 @@ use super::{statements::AllStatements, AllNodes};
 use std::fmt::Display;
 
 /// Program node is going to be the root node of every AST that the parser produces
+#[derive(Default, Clone)]
 pub struct Program {
     pub statements: Vec<AllStatements>,
 }
@@ -3,6 +3,9 @@ use std::fmt::Display;
 use super::expressions::{self, AllExpressions};
 use crate::lexer::token;
 
+/// The indentation used for each nesting level when rendering blocks
+const INDENT: &str = "    ";
+
 #[derive(PartialEq, Eq, Hash, Clone)]
 pub enum AllStatements {
     Let(LetStatement),
@@ -10,6 +13,8 @@ pub enum AllStatements {
     Expression(ExpressionStatement),
     Block(BlockStatement),
     While(WhileStatement),
+    Loop(LoopStatement),
+    Break(BreakStatement),
 }
 
 impl Display for AllStatements {
@@ -20,23 +25,76 @@ impl Display for AllStatements {
             AllStatements::Expression(v) => v.to_string(),
             AllStatements::Block(v) => v.to_string(),
             AllStatements::While(v) => v.to_string(),
+            AllStatements::Loop(v) => v.to_string(),
+            AllStatements::Break(v) => v.to_string(),
         };
 
         write!(f, "{}", out)
     }
 }
 
+impl AllStatements {
+    /// Returns the first token of the statement, which holds the position of the statement in the source
+    pub fn token(&self) -> &token::Token {
+        match self {
+            AllStatements::Let(v) => &v.token,
+            AllStatements::Return(v) => &v.token,
+            AllStatements::Expression(v) => &v.token,
+            AllStatements::Block(v) => &v.token,
+            AllStatements::While(v) => &v.token,
+            AllStatements::Loop(v) => &v.token,
+            AllStatements::Break(v) => &v.token,
+        }
+    }
+
+    /// Renders the statement, terminating expression statements with a semicolon so that
+    /// the output can be parsed back.
+    pub fn to_terminated_string(&self) -> String {
+        let mut out = self.to_string();
+        if let AllStatements::Expression(_) = self {
+            out.push(';');
+        }
+        out
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Clone)]
 pub struct LetStatement {
     pub token: token::Token, // Let token
-    pub name: expressions::Identifier,
+    pub target: LetTarget,
     pub value: Box<AllExpressions>,
 }
 
+/// The names bound by a let statement
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub enum LetTarget {
+    Identifier(expressions::Identifier),
+    /// binds each name to the array element at the same position, as in `let [a, b] = arr;`
+    Array(Vec<expressions::Identifier>),
+    /// binds each name to the value of the hash map under the same string key, as in `let {a, b} = m;`
+    HashMap(Vec<expressions::Identifier>),
+}
+
+impl Display for LetTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LetTarget::Identifier(v) => write!(f, "{}", v),
+            LetTarget::Array(names) => {
+                let names = names.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+                write!(f, "[{}]", names.join(", "))
+            }
+            LetTarget::HashMap(names) => {
+                let names = names.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+                write!(f, "{{{}}}", names.join(", "))
+            }
+        }
+    }
+}
+
 impl Display for LetStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut out = String::new();
-        out.push_str(format!("let {} = ", self.name).as_str());
+        out.push_str(format!("let {} = ", self.target).as_str());
 
         out.push_str(self.value.to_string().as_str());
         out.push(';');
@@ -94,6 +152,42 @@ impl Display for BlockStatement {
     }
 }
 
+impl BlockStatement {
+    /// Renders the block surrounded by braces, with each statement on its own line indented by one level.
+    pub fn to_braced_string(&self) -> String {
+        if self.statements.is_empty() {
+            return "{}".to_string();
+        }
+
+        let mut out = String::from("{\n");
+        for stmt in &self.statements {
+            indent_statement(&stmt.to_terminated_string(), &mut out);
+        }
+        out.push('}');
+
+        out
+    }
+}
+
+/// Appends the rendered statement to the output indented by one level. Nested blocks are already
+/// indented, so every line gets one more level, except for the lines continuing a multi-line string
+/// literal, whose contents are left as they are. String literals can't contain quotes, so each quote
+/// either opens or closes one.
+fn indent_statement(stmt: &str, out: &mut String) {
+    let mut in_string = false;
+    for line in stmt.split('\n') {
+        if !in_string {
+            out.push_str(INDENT);
+        }
+        out.push_str(line);
+        out.push('\n');
+
+        if line.matches('"').count() % 2 == 1 {
+            in_string = !in_string;
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Clone)]
 pub struct WhileStatement {
     pub token: token::Token,
@@ -103,7 +197,39 @@ pub struct WhileStatement {
 
 impl Display for WhileStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let out = format!("while ({}) {{\n {} }}", self.condition, self.body);
+        let out = format!(
+            "while {} {}",
+            expressions::parenthesized(&self.condition),
+            self.body.to_braced_string()
+        );
         write!(f, "{}", out)
     }
 }
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct LoopStatement {
+    pub token: token::Token,
+    pub body: BlockStatement,
+}
+
+impl Display for LoopStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "loop {}", self.body.to_braced_string())
+    }
+}
+
+/// Breaks out of the enclosing loop, optionally with a value which becomes the result of the loop
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct BreakStatement {
+    pub token: token::Token,
+    pub value: Option<Box<AllExpressions>>,
+}
+
+impl Display for BreakStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.value {
+            Some(v) => write!(f, "break {};", v),
+            None => write!(f, "break;"),
+        }
+    }
+}
@@ -1,13 +1,16 @@
-use super::{errors, helpers};
+use super::{errors, eval::apply_function, helpers, json};
 use crate::{
     ast::expressions::Identifier,
     object::{
-        objects::{BuiltinFunctionObj, ParamsType},
-        AllObjects, Object, ObjectType,
+        objects::{
+            ArrayObj, BuiltinFunctionObj, HashMapObj, NativeClosureObj, ParamsType,
+            StringBuilderObj,
+        },
+        AllObjects, Object,
     },
     Environment,
 };
-use std::rc::Rc;
+use std::{cell::RefCell, cmp::Ordering, collections::HashMap, io::Write, rc::Rc};
 use std::{thread, time::Duration};
 
 /// Return the associated builtin function based on the function name
@@ -18,11 +21,21 @@ pub fn get_builtin_function(ident: &Identifier) -> Option<AllObjects> {
             parameters: ParamsType::Fixed(vec!["value".to_string()]),
             func: len,
         },
+        "size" => BuiltinFunctionObj {
+            fn_name: "size".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: len,
+        },
         "print" => BuiltinFunctionObj {
             fn_name: "print".to_string(),
             parameters: ParamsType::Variadic,
             func: print,
         },
+        "debug" => BuiltinFunctionObj {
+            fn_name: "debug".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: debug,
+        },
         "push" => BuiltinFunctionObj {
             fn_name: "push".to_string(),
             parameters: ParamsType::Fixed(vec!["array".to_string(), "element".to_string()]),
@@ -33,11 +46,60 @@ pub fn get_builtin_function(ident: &Identifier) -> Option<AllObjects> {
             parameters: ParamsType::Fixed(vec!["array".to_string()]),
             func: pop,
         },
+        "push_front" => BuiltinFunctionObj {
+            fn_name: "push_front".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string(), "element".to_string()]),
+            func: push_front,
+        },
+        "pop_front" => BuiltinFunctionObj {
+            fn_name: "pop_front".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string()]),
+            func: pop_front,
+        },
+        "insert_at" => BuiltinFunctionObj {
+            fn_name: "insert_at".to_string(),
+            parameters: ParamsType::Fixed(vec![
+                "array".to_string(),
+                "index".to_string(),
+                "element".to_string(),
+            ]),
+            func: insert_at,
+        },
+        "remove_at" => BuiltinFunctionObj {
+            fn_name: "remove_at".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string(), "index".to_string()]),
+            func: remove_at,
+        },
         "is_null" => BuiltinFunctionObj {
             fn_name: "is_null".to_string(),
             parameters: ParamsType::Fixed(vec!["value".to_string()]),
             func: is_null,
         },
+        "is_array" => BuiltinFunctionObj {
+            fn_name: "is_array".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: is_array,
+        },
+        "is_string" => BuiltinFunctionObj {
+            fn_name: "is_string".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: is_string,
+        },
+        "is_number" => BuiltinFunctionObj {
+            fn_name: "is_number".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: is_number,
+        },
+        "is_map" => BuiltinFunctionObj {
+            fn_name: "is_map".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: is_map,
+        },
+        "is_function" => BuiltinFunctionObj {
+            fn_name: "is_function".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: is_function,
+        },
         "insert" => BuiltinFunctionObj {
             fn_name: "insert".to_string(),
             parameters: ParamsType::Fixed(vec![
@@ -52,11 +114,368 @@ pub fn get_builtin_function(ident: &Identifier) -> Option<AllObjects> {
             parameters: ParamsType::Fixed(vec!["map".to_string(), "key".to_string()]),
             func: delete,
         },
+        "assoc" => BuiltinFunctionObj {
+            fn_name: "assoc".to_string(),
+            parameters: ParamsType::Fixed(vec![
+                "map".to_string(),
+                "key".to_string(),
+                "value".to_string(),
+            ]),
+            func: assoc,
+        },
+        "dissoc" => BuiltinFunctionObj {
+            fn_name: "dissoc".to_string(),
+            parameters: ParamsType::Fixed(vec!["map".to_string(), "key".to_string()]),
+            func: dissoc,
+        },
+        "merge" => BuiltinFunctionObj {
+            fn_name: "merge".to_string(),
+            parameters: ParamsType::Fixed(vec!["map".to_string(), "other".to_string()]),
+            func: merge,
+        },
         "sleep" => BuiltinFunctionObj {
             fn_name: "sleep".to_string(),
             parameters: ParamsType::Fixed(vec!["seconds".to_string()]),
             func: sleep,
         },
+        "sleep_ms" => BuiltinFunctionObj {
+            fn_name: "sleep_ms".to_string(),
+            parameters: ParamsType::Fixed(vec!["milliseconds".to_string()]),
+            func: sleep_ms,
+        },
+        "globals" => BuiltinFunctionObj {
+            fn_name: "globals".to_string(),
+            parameters: ParamsType::Fixed(vec![]),
+            func: globals,
+        },
+        "slice" => BuiltinFunctionObj {
+            fn_name: "slice".to_string(),
+            parameters: ParamsType::Fixed(vec![
+                "collection".to_string(),
+                "start".to_string(),
+                "end".to_string(),
+            ]),
+            func: slice,
+        },
+        "take" => BuiltinFunctionObj {
+            fn_name: "take".to_string(),
+            parameters: ParamsType::Fixed(vec!["collection".to_string(), "n".to_string()]),
+            func: take,
+        },
+        "drop" => BuiltinFunctionObj {
+            fn_name: "drop".to_string(),
+            parameters: ParamsType::Fixed(vec!["collection".to_string(), "n".to_string()]),
+            func: drop,
+        },
+        "concat" => BuiltinFunctionObj {
+            fn_name: "concat".to_string(),
+            parameters: ParamsType::Variadic,
+            func: concat,
+        },
+        "copy" => BuiltinFunctionObj {
+            fn_name: "copy".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: copy,
+        },
+        "each" => BuiltinFunctionObj {
+            fn_name: "each".to_string(),
+            parameters: ParamsType::Fixed(vec!["collection".to_string(), "callback".to_string()]),
+            func: each,
+        },
+        "sum" => BuiltinFunctionObj {
+            fn_name: "sum".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string()]),
+            func: sum,
+        },
+        "product" => BuiltinFunctionObj {
+            fn_name: "product".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string()]),
+            func: product,
+        },
+        "max_int" => BuiltinFunctionObj {
+            fn_name: "max_int".to_string(),
+            parameters: ParamsType::Fixed(vec![]),
+            func: max_int,
+        },
+        "min_int" => BuiltinFunctionObj {
+            fn_name: "min_int".to_string(),
+            parameters: ParamsType::Fixed(vec![]),
+            func: min_int,
+        },
+        "gcd" => BuiltinFunctionObj {
+            fn_name: "gcd".to_string(),
+            parameters: ParamsType::Fixed(vec!["a".to_string(), "b".to_string()]),
+            func: gcd,
+        },
+        "lcm" => BuiltinFunctionObj {
+            fn_name: "lcm".to_string(),
+            parameters: ParamsType::Fixed(vec!["a".to_string(), "b".to_string()]),
+            func: lcm,
+        },
+        "bit_count" => BuiltinFunctionObj {
+            fn_name: "bit_count".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: bit_count,
+        },
+        "leading_zeros" => BuiltinFunctionObj {
+            fn_name: "leading_zeros".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: leading_zeros,
+        },
+        "trailing_zeros" => BuiltinFunctionObj {
+            fn_name: "trailing_zeros".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: trailing_zeros,
+        },
+        "chunk" => BuiltinFunctionObj {
+            fn_name: "chunk".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string(), "size".to_string()]),
+            func: chunk,
+        },
+        "step_by" => BuiltinFunctionObj {
+            fn_name: "step_by".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string(), "step".to_string()]),
+            func: step_by,
+        },
+        "zip" => BuiltinFunctionObj {
+            fn_name: "zip".to_string(),
+            parameters: ParamsType::Fixed(vec!["first".to_string(), "second".to_string()]),
+            func: zip,
+        },
+        "repeat" => BuiltinFunctionObj {
+            fn_name: "repeat".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string(), "count".to_string()]),
+            func: repeat,
+        },
+        "repeat_str" => BuiltinFunctionObj {
+            fn_name: "repeat_str".to_string(),
+            parameters: ParamsType::Fixed(vec![
+                "value".to_string(),
+                "count".to_string(),
+                "separator".to_string(),
+            ]),
+            func: repeat_str,
+        },
+        "min_by" => BuiltinFunctionObj {
+            fn_name: "min_by".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string(), "key_fn".to_string()]),
+            func: min_by,
+        },
+        "max_by" => BuiltinFunctionObj {
+            fn_name: "max_by".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string(), "key_fn".to_string()]),
+            func: max_by,
+        },
+        "find" => BuiltinFunctionObj {
+            fn_name: "find".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string(), "predicate".to_string()]),
+            func: find,
+        },
+        "find_index" => BuiltinFunctionObj {
+            fn_name: "find_index".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string(), "predicate".to_string()]),
+            func: find_index,
+        },
+        "all" => BuiltinFunctionObj {
+            fn_name: "all".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string(), "predicate".to_string()]),
+            func: all,
+        },
+        "any" => BuiltinFunctionObj {
+            fn_name: "any".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string(), "predicate".to_string()]),
+            func: any,
+        },
+        "flat" => BuiltinFunctionObj {
+            fn_name: "flat".to_string(),
+            parameters: ParamsType::Optional(vec!["array".to_string()], vec!["depth".to_string()]),
+            func: flat,
+        },
+        "count" => BuiltinFunctionObj {
+            fn_name: "count".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string(), "value".to_string()]),
+            func: count,
+        },
+        "max" => BuiltinFunctionObj {
+            fn_name: "max".to_string(),
+            parameters: ParamsType::Minimum(1),
+            func: max,
+        },
+        "min" => BuiltinFunctionObj {
+            fn_name: "min".to_string(),
+            parameters: ParamsType::Minimum(1),
+            func: min,
+        },
+        "panic" => BuiltinFunctionObj {
+            fn_name: "panic".to_string(),
+            parameters: ParamsType::Fixed(vec!["message".to_string()]),
+            func: panic,
+        },
+        "exit" => BuiltinFunctionObj {
+            fn_name: "exit".to_string(),
+            parameters: ParamsType::Fixed(vec!["code".to_string()]),
+            func: exit,
+        },
+        "ok" => BuiltinFunctionObj {
+            fn_name: "ok".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: ok,
+        },
+        "err" => BuiltinFunctionObj {
+            fn_name: "err".to_string(),
+            parameters: ParamsType::Fixed(vec!["message".to_string()]),
+            func: err,
+        },
+        "is_ok" => BuiltinFunctionObj {
+            fn_name: "is_ok".to_string(),
+            parameters: ParamsType::Fixed(vec!["result".to_string()]),
+            func: is_ok,
+        },
+        "is_err" => BuiltinFunctionObj {
+            fn_name: "is_err".to_string(),
+            parameters: ParamsType::Fixed(vec!["result".to_string()]),
+            func: is_err,
+        },
+        "unwrap" => BuiltinFunctionObj {
+            fn_name: "unwrap".to_string(),
+            parameters: ParamsType::Fixed(vec!["result".to_string()]),
+            func: unwrap,
+        },
+        "parse_int" => BuiltinFunctionObj {
+            fn_name: "parse_int".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string(), "radix".to_string()]),
+            func: parse_int,
+        },
+        "ord" => BuiltinFunctionObj {
+            fn_name: "ord".to_string(),
+            parameters: ParamsType::Fixed(vec!["char".to_string()]),
+            func: ord,
+        },
+        "chr" => BuiltinFunctionObj {
+            fn_name: "chr".to_string(),
+            parameters: ParamsType::Fixed(vec!["code".to_string()]),
+            func: chr,
+        },
+        "to_radix" => BuiltinFunctionObj {
+            fn_name: "to_radix".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string(), "radix".to_string()]),
+            func: to_radix,
+        },
+        "inspect_sorted" => BuiltinFunctionObj {
+            fn_name: "inspect_sorted".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: inspect_sorted,
+        },
+        "args" => BuiltinFunctionObj {
+            fn_name: "args".to_string(),
+            parameters: ParamsType::Fixed(vec![]),
+            func: args,
+        },
+        "getenv" => BuiltinFunctionObj {
+            fn_name: "getenv".to_string(),
+            parameters: ParamsType::Fixed(vec!["name".to_string()]),
+            func: getenv,
+        },
+        "lines" => BuiltinFunctionObj {
+            fn_name: "lines".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: lines,
+        },
+        "parse_csv_line" => BuiltinFunctionObj {
+            fn_name: "parse_csv_line".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: parse_csv_line,
+        },
+        "from_chars" => BuiltinFunctionObj {
+            fn_name: "from_chars".to_string(),
+            parameters: ParamsType::Fixed(vec!["array".to_string()]),
+            func: from_chars,
+        },
+        "pad_left" => BuiltinFunctionObj {
+            fn_name: "pad_left".to_string(),
+            parameters: pad_parameters(),
+            func: pad_left,
+        },
+        "pad_right" => BuiltinFunctionObj {
+            fn_name: "pad_right".to_string(),
+            parameters: pad_parameters(),
+            func: pad_right,
+        },
+        "center" => BuiltinFunctionObj {
+            fn_name: "center".to_string(),
+            parameters: pad_parameters(),
+            func: center,
+        },
+        "string_builder" => BuiltinFunctionObj {
+            fn_name: "string_builder".to_string(),
+            parameters: ParamsType::Fixed(vec![]),
+            func: string_builder,
+        },
+        "append" => BuiltinFunctionObj {
+            fn_name: "append".to_string(),
+            parameters: ParamsType::Fixed(vec!["builder".to_string(), "value".to_string()]),
+            func: append,
+        },
+        "build" => BuiltinFunctionObj {
+            fn_name: "build".to_string(),
+            parameters: ParamsType::Fixed(vec!["builder".to_string()]),
+            func: build,
+        },
+        "trim" => BuiltinFunctionObj {
+            fn_name: "trim".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: trim,
+        },
+        "trim_start" => BuiltinFunctionObj {
+            fn_name: "trim_start".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: trim_start,
+        },
+        "trim_end" => BuiltinFunctionObj {
+            fn_name: "trim_end".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: trim_end,
+        },
+        "trim_chars" => BuiltinFunctionObj {
+            fn_name: "trim_chars".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string(), "chars".to_string()]),
+            func: trim_chars,
+        },
+        "entries" => BuiltinFunctionObj {
+            fn_name: "entries".to_string(),
+            parameters: ParamsType::Fixed(vec!["map".to_string()]),
+            func: entries,
+        },
+        "sorted_keys" => BuiltinFunctionObj {
+            fn_name: "sorted_keys".to_string(),
+            parameters: ParamsType::Fixed(vec!["map".to_string()]),
+            func: sorted_keys,
+        },
+        "json_stringify" => BuiltinFunctionObj {
+            fn_name: "json_stringify".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: json_stringify,
+        },
+        "json_parse" => BuiltinFunctionObj {
+            fn_name: "json_parse".to_string(),
+            parameters: ParamsType::Fixed(vec!["value".to_string()]),
+            func: json_parse,
+        },
+        "compose" => BuiltinFunctionObj {
+            fn_name: "compose".to_string(),
+            parameters: ParamsType::Fixed(vec!["f".to_string(), "g".to_string()]),
+            func: compose,
+        },
+        "pipe" => BuiltinFunctionObj {
+            fn_name: "pipe".to_string(),
+            parameters: ParamsType::Minimum(1),
+            func: pipe,
+        },
+        "partial" => BuiltinFunctionObj {
+            fn_name: "partial".to_string(),
+            parameters: ParamsType::Minimum(1),
+            func: partial,
+        },
         _ => return None,
     };
 
@@ -65,15 +484,17 @@ pub fn get_builtin_function(ident: &Identifier) -> Option<AllObjects> {
 
 /// Returns the length of a string, an array or a hashmap.
 ///
-/// The function expects an argument called value, which must be one of the said types.
-pub fn len(env: Rc<Environment>) -> AllObjects {
+/// The function expects an argument called value, which must be one of the said types. It's also
+/// available as `size`.
+pub fn len(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
     let value = get_argument("value", env);
 
     let length = match value {
         AllObjects::StringObj(v) => v.value.len(),
         AllObjects::ArrayObj(v) => v.elements.borrow().len(),
+        AllObjects::HashMap(v) => v.map.borrow().len(),
         AllObjects::Error(_) => return value,
-        v => return errors::unexpected_argument_type("a STRING", v),
+        v => return errors::unexpected_argument_type("a STRING, an ARRAY or a HASH_MAP", v),
     };
 
     // panic of conversion from usize to i64 is highly unlikely
@@ -85,30 +506,48 @@ pub fn len(env: Rc<Environment>) -> AllObjects {
 /// Takes a variable number of arguments and prints each one consecutively to the stdout with a single space separator.
 ///
 /// If no arguments are provided, it will print a newline.
-pub fn print(env: Rc<Environment>) -> AllObjects {
+pub fn print(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
     let all_vars = env.all_vars();
+    let mut output = Vec::with_capacity(all_vars.len());
 
-    for (i, var) in all_vars.iter().enumerate() {
-        let arg = match env.get(var) {
-            Some(v) => v,
+    for var in all_vars.iter() {
+        match env.get(var) {
+            Some(v) => output.push(v.inspect()),
             None => return errors::identifier_not_found(var),
         };
-
-        print!("{}", arg.inspect());
-        if i != all_vars.len() - 1 {
-            print!(" ");
-        }
     }
 
-    if all_vars.is_empty() {
-        println!();
+    let output = match output.is_empty() {
+        true => "\n".to_string(),
+        false => output.join(" "),
+    };
+
+    if let Some(limit) = &caller.settings().output_limit {
+        if !limit.try_write(output.len()) {
+            return errors::output_limit_exceeded();
+        }
     }
 
+    print!("{}", output);
     helpers::NULL
 }
 
+/// Writes the value to stderr, or to the debug writer in the settings, prefixed with `[debug]`, and
+/// returns the value unchanged, so that it can be wrapped around any expression. Unlike `print`, the
+/// output doesn't count against the output limit, and failing to write it is ignored.
+pub fn debug(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    let value = get_argument("value", env);
+    let line = format!("[debug] {}\n", value.inspect());
+
+    let _ = match &caller.settings().debug_writer {
+        Some(writer) => writer.borrow_mut().write_all(line.as_bytes()),
+        None => std::io::stderr().write_all(line.as_bytes()),
+    };
+    value
+}
+
 /// Appends an element to the back of the array
-pub fn push(env: Rc<Environment>) -> AllObjects {
+pub fn push(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
     let array = get_argument("array", env.clone());
     let element = get_argument("element", env);
 
@@ -126,7 +565,7 @@ pub fn push(env: Rc<Environment>) -> AllObjects {
 /// Removes the last element from an array and returns it.
 ///
 /// Returns null, if the array is empty
-pub fn pop(env: Rc<Environment>) -> AllObjects {
+pub fn pop(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
     let array = get_argument("array", env);
 
     let array = match array {
@@ -143,18 +582,116 @@ pub fn pop(env: Rc<Environment>) -> AllObjects {
     popped
 }
 
+/// Inserts an element at the front of the array
+pub fn push_front(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let array = get_argument("array", env.clone());
+    let element = get_argument("element", env);
+
+    let array = match array {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+
+    array.elements.borrow_mut().insert(0, element);
+
+    helpers::NULL
+}
+
+/// Removes the first element from an array and returns it.
+///
+/// Returns null, if the array is empty
+pub fn pop_front(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let array = match get_argument("array", env) {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+
+    let mut elements = array.elements.borrow_mut();
+    match elements.is_empty() {
+        true => helpers::NULL,
+        false => elements.remove(0),
+    }
+}
+
+/// Inserts an element into the array at the given index, shifting the following elements to the right.
+///
+/// The index can be equal to the length of the array, which appends the element.
+pub fn insert_at(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let array = match get_argument("array", env.clone()) {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+    let index = match get_integer_argument("index", env.clone()) {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let element = get_argument("element", env);
+
+    let mut elements = array.elements.borrow_mut();
+    match usize::try_from(index) {
+        Ok(index) if index <= elements.len() => elements.insert(index, element),
+        _ => return errors::indexing_error(),
+    }
+
+    helpers::NULL
+}
+
+/// Removes the element at the given index from the array and returns it, shifting the following
+/// elements to the left
+pub fn remove_at(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let array = match get_argument("array", env.clone()) {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+    let index = match get_integer_argument("index", env) {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let mut elements = array.elements.borrow_mut();
+    match usize::try_from(index) {
+        Ok(index) if index < elements.len() => elements.remove(index),
+        _ => errors::indexing_error(),
+    }
+}
+
 /// Checks if the passed value is a null
-pub fn is_null(env: Rc<Environment>) -> AllObjects {
+pub fn is_null(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
     let is_null = matches!(get_argument("value", env), AllObjects::Null(_));
     helpers::get_bool_consts(is_null)
 }
 
+/// Checks if the passed value is an array
+pub fn is_array(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    helpers::get_bool_consts(get_argument("value", env).is_array())
+}
+
+/// Checks if the passed value is a string
+pub fn is_string(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    helpers::get_bool_consts(get_argument("value", env).is_string())
+}
+
+/// Checks if the passed value is a number
+pub fn is_number(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    helpers::get_bool_consts(get_argument("value", env).is_integer())
+}
+
+/// Checks if the passed value is a hash map
+pub fn is_map(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    helpers::get_bool_consts(get_argument("value", env).is_hash_map())
+}
+
+/// Checks if the passed value is a user defined or a builtin function
+pub fn is_function(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    helpers::get_bool_consts(get_argument("value", env).is_function())
+}
+
 /// Inserts a key-value pair into the map.
 ///
 /// If the map did not have this key present, Null is returned.
 ///
 /// If the map did have this key present, the value is updated, and the old value is returned
-pub fn insert(env: Rc<Environment>) -> AllObjects {
+pub fn insert(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
     let map_arg = get_argument("map", env.clone());
     let key = get_argument("key", env.clone());
     let value = get_argument("value", env);
@@ -163,6 +700,9 @@ pub fn insert(env: Rc<Environment>) -> AllObjects {
         AllObjects::HashMap(v) => v,
         v => return errors::unexpected_argument_type("a hash map", v),
     };
+    if let Err(e) = helpers::check_hash_key(&key) {
+        return e;
+    }
 
     if let Some(v) = m.map.borrow_mut().insert(key, value) {
         return v;
@@ -173,7 +713,7 @@ pub fn insert(env: Rc<Environment>) -> AllObjects {
 
 /// Removes a key from the map, returning the value at the key if the key was previously in the map and
 /// returns Null otherwise
-pub fn delete(env: Rc<Environment>) -> AllObjects {
+pub fn delete(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
     let map_arg = get_argument("map", env.clone());
     let key = get_argument("key", env.clone());
 
@@ -189,8 +729,64 @@ pub fn delete(env: Rc<Environment>) -> AllObjects {
     helpers::NULL
 }
 
+/// Returns a new map with the key set to the value, leaving the given map untouched. Unlike insert,
+/// other variables holding the same map don't see the change.
+pub fn assoc(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let key = get_argument("key", env.clone());
+    let value = get_argument("value", env.clone());
+    if let Err(e) = helpers::check_hash_key(&key) {
+        return e;
+    }
+
+    updated_map(env, |map| {
+        map.insert(key, value);
+    })
+}
+
+/// Returns a new map without the key, leaving the given map untouched. Unlike delete, other
+/// variables holding the same map don't see the change.
+pub fn dissoc(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let key = get_argument("key", env.clone());
+
+    updated_map(env, |map| {
+        map.remove(&key);
+    })
+}
+
+/// Returns a new map with the entries of both maps. The second map's values win when both maps have
+/// the same key, and neither of the given maps is changed.
+pub fn merge(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let other = match get_argument("other", env.clone()) {
+        AllObjects::HashMap(v) => v,
+        v => return errors::unexpected_argument_type("a hash map", v),
+    };
+
+    updated_map(env, |map| {
+        let entries = other.map.borrow();
+        map.extend(entries.iter().map(|(k, v)| (k.clone(), v.clone())));
+    })
+}
+
+/// Applies the update to a copy of the map argument's entries, and returns the copy as a new map
+// the keys are cloned from the map argument, so they hash the same as they do there
+#[allow(clippy::mutable_key_type)]
+fn updated_map(
+    env: Rc<Environment>,
+    update: impl FnOnce(&mut HashMap<AllObjects, AllObjects>),
+) -> AllObjects {
+    let mut map = match get_argument("map", env) {
+        AllObjects::HashMap(v) => v.map.borrow().clone(),
+        v => return errors::unexpected_argument_type("a hash map", v),
+    };
+    update(&mut map);
+
+    AllObjects::HashMap(HashMapObj {
+        map: Rc::new(RefCell::new(map)),
+    })
+}
+
 /// Puts the main thread to sleep for at least the specified amount of time given in seconds
-pub fn sleep(env: Rc<Environment>) -> AllObjects {
+pub fn sleep(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
     let seconds = match get_argument("seconds", env.clone()) {
         AllObjects::Integer(n) => n,
         v => return errors::unexpected_argument_type("an integer", v),
@@ -205,9 +801,1383 @@ pub fn sleep(env: Rc<Environment>) -> AllObjects {
     helpers::NULL
 }
 
-fn get_argument(arg_name: &str, env: Rc<Environment>) -> AllObjects {
-    match env.get(arg_name) {
-        Some(v) => v,
-        None => errors::argument_not_found("value", ObjectType::String),
+/// Puts the main thread to sleep for at least the specified amount of time given in milliseconds
+pub fn sleep_ms(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let milliseconds = match get_argument("milliseconds", env) {
+        AllObjects::Integer(n) => n,
+        v => return errors::unexpected_argument_type("an integer", v),
+    };
+
+    let Ok(milliseconds) = TryInto::<u64>::try_into(milliseconds.value) else {
+        return errors::sleep_arg_error();
+    };
+
+    thread::sleep(Duration::from_millis(milliseconds));
+
+    helpers::NULL
+}
+
+/// Returns a hash map of all the variables visible from the calling scope, keyed by their names.
+///
+/// Outer scopes are walked as well, so calling it within a function includes both the local and the
+/// global variables. If a name is shadowed, the innermost value is used.
+// the keys are strings, which have no interior mutability
+#[allow(clippy::mutable_key_type)]
+pub fn globals(_env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    let mut map = HashMap::new();
+
+    for name in caller.visible_vars() {
+        if let Some(value) = caller.get(&name) {
+            map.insert(helpers::get_string_object_for_value(name), value);
+        }
+    }
+
+    AllObjects::HashMap(HashMapObj {
+        map: Rc::new(RefCell::new(map)),
+    })
+}
+
+/// Returns a copy of the portion of an array or a string between the start and the end indices.
+///
+/// Unlike range indexing, out of bound indices are clamped to the bounds of the collection instead of
+/// producing an error, and a start index greater than the end index results in an empty collection.
+pub fn slice(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let collection = get_argument("collection", env.clone());
+    let start = match get_integer_argument("start", env.clone()) {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let end = match get_integer_argument("end", env) {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let length = match &collection {
+        AllObjects::ArrayObj(v) => v.elements.borrow().len(),
+        AllObjects::StringObj(v) => v.value.chars().count(),
+        v => return errors::unexpected_argument_type("an ARRAY or a STRING", v.clone()),
+    };
+
+    let clamp = |index: i64| usize::try_from(index.max(0)).map_or(length, |v| v.min(length));
+    let start = clamp(start);
+    let end = clamp(end).max(start);
+
+    match collection {
+        AllObjects::StringObj(v) => helpers::get_string_index_value(v, start, Some(end)),
+        AllObjects::ArrayObj(v) => helpers::get_array_index_value(v, start, Some(end)),
+        v => v,
+    }
+}
+
+/// Returns the first `n` elements of an array, or the first `n` characters of a string.
+///
+/// `n` is clamped to the length of the collection, so the whole collection is returned if it's shorter.
+pub fn take(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    match split_collection(env) {
+        Ok((first, _)) => first,
+        Err(e) => e,
+    }
+}
+
+/// Returns the elements of an array, or the characters of a string, after the first `n`.
+///
+/// `n` is clamped to the length of the collection, so an empty collection is returned if it's shorter.
+pub fn drop(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    match split_collection(env) {
+        Ok((_, rest)) => rest,
+        Err(e) => e,
+    }
+}
+
+/// Splits the collection after its first `n` elements or characters, with negative counts treated as zero
+fn split_collection(env: Rc<Environment>) -> Result<(AllObjects, AllObjects), AllObjects> {
+    let collection = get_argument("collection", env.clone());
+    let n = usize::try_from(get_integer_argument("n", env)?.max(0)).unwrap_or(usize::MAX);
+
+    match collection {
+        AllObjects::ArrayObj(v) => {
+            let elements = v.elements.borrow();
+            let (first, rest) = elements.split_at(n.min(elements.len()));
+            let to_array = |elements: &[AllObjects]| {
+                AllObjects::ArrayObj(ArrayObj {
+                    elements: Rc::new(RefCell::new(elements.to_vec())),
+                })
+            };
+            Ok((to_array(first), to_array(rest)))
+        }
+        AllObjects::StringObj(v) => {
+            let at = v
+                .value
+                .char_indices()
+                .nth(n)
+                .map_or(v.value.len(), |(i, _)| i);
+            let (first, rest) = v.value.split_at(at);
+            Ok((
+                helpers::get_string_object_for_value(first.to_string()),
+                helpers::get_string_object_for_value(rest.to_string()),
+            ))
+        }
+        v => Err(errors::unexpected_argument_type("an ARRAY or a STRING", v)),
+    }
+}
+
+/// Takes a variable number of arrays and returns a new array containing the elements of all of them in order.
+///
+/// Returns an empty array if no arguments are provided.
+pub fn concat(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let mut elements = Vec::new();
+
+    for arg in get_variadic_arguments(env) {
+        match arg {
+            AllObjects::ArrayObj(v) => elements.extend(v.elements.borrow().iter().cloned()),
+            v => return errors::unexpected_argument_type("an ARRAY", v),
+        }
+    }
+
+    AllObjects::ArrayObj(ArrayObj {
+        elements: Rc::new(RefCell::new(elements)),
+    })
+}
+
+/// Aborts the whole program with the given message.
+///
+/// Unlike the other errors, the fatal error cannot be recovered from.
+pub fn panic(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let message = match get_argument("message", env) {
+        AllObjects::StringObj(v) => v.value.to_string(),
+        v => v.inspect(),
+    };
+    AllObjects::new_fatal_error(&message)
+}
+
+/// Stops the program, which then ends with the given status code. Only codes between 0 and 255 can
+/// be reported to the operating system, so any other code is an error.
+pub fn exit(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    match get_integer_argument("code", env) {
+        Ok(code) if (0..=255).contains(&code) => AllObjects::ExitSignal(code),
+        Ok(code) => errors::exit_code_error(code),
+        Err(e) => e,
+    }
+}
+
+/// The key which tags a result map as a successful result holding a value
+const OK_KEY: &str = "__ok__";
+
+/// The key which tags a result map as a failed result holding an error message
+const ERR_KEY: &str = "__err__";
+
+/// Returns a successful result, which is a hash map holding the value under the `"__ok__"` key
+pub fn ok(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    new_result(OK_KEY, get_argument("value", env))
+}
+
+/// Returns a failed result, which is a hash map holding the message under the `"__err__"` key
+pub fn err(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    new_result(ERR_KEY, get_argument("message", env))
+}
+
+/// Checks if the passed value is a result created by `ok`
+pub fn is_ok(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let result = get_result(&get_argument("result", env));
+    helpers::get_bool_consts(matches!(result, Some(Ok(_))))
+}
+
+/// Checks if the passed value is a result created by `err`
+pub fn is_err(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let result = get_result(&get_argument("result", env));
+    helpers::get_bool_consts(matches!(result, Some(Err(_))))
+}
+
+/// Returns the value of a successful result, or an error with the message of a failed result
+pub fn unwrap(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let result = get_argument("result", env);
+    match get_result(&result) {
+        Some(Ok(value)) => value,
+        Some(Err(message)) => errors::unwrap_err(&message),
+        None if result.is_error() => result,
+        None => errors::unexpected_argument_type("a result", result),
+    }
+}
+
+fn new_result(key: &str, value: AllObjects) -> AllObjects {
+    if value.is_error() {
+        return value;
+    }
+
+    let key = helpers::get_string_object_for_value(key.to_string());
+    AllObjects::HashMap(HashMapObj {
+        map: Rc::new(RefCell::new(HashMap::from([(key, value)]))),
+    })
+}
+
+/// Returns the value or the message held by a result map, or None if the value isn't a result
+fn get_result(value: &AllObjects) -> Option<Result<AllObjects, AllObjects>> {
+    let AllObjects::HashMap(m) = value else {
+        return None;
+    };
+
+    let map = m.map.borrow();
+    let get = |key: &str| map.get(&helpers::get_string_object_for_value(key.to_string()));
+    match (get(OK_KEY), get(ERR_KEY)) {
+        (Some(v), None) => Some(Ok(v.clone())),
+        (None, Some(v)) => Some(Err(v.clone())),
+        _ => None,
+    }
+}
+
+/// Parses a string as an integer in the given radix, which must be between 2 and 36.
+///
+/// Letters are used for digits above 9 in either case, so `parse_int("ff", 16)` returns 255.
+pub fn parse_int(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let value = match get_argument("value", env.clone()) {
+        AllObjects::StringObj(v) => v.value,
+        v => return errors::unexpected_argument_type("a STRING", v),
+    };
+
+    let radix = match get_integer_argument("radix", env) {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    if !(2..=36).contains(&radix) {
+        return errors::radix_error(radix);
+    }
+
+    match i64::from_str_radix(&value, radix as u32) {
+        Ok(v) => helpers::get_int_object_for_value(v),
+        Err(_) => errors::invalid_integer_literal(&value, radix),
+    }
+}
+
+/// Renders the integer in the given radix with lowercase digits, which is the inverse of `parse_int`.
+/// Negative numbers are prefixed with `-`.
+pub fn to_radix(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let value = match get_integer_argument("value", env.clone()) {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let radix = match get_integer_argument("radix", env) {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    if !(2..=36).contains(&radix) {
+        return errors::radix_error(radix);
+    }
+
+    let mut magnitude = value.unsigned_abs();
+    let mut digits = Vec::new();
+    loop {
+        let digit = (magnitude % radix as u64) as u32;
+        digits.push(char::from_digit(digit, radix as u32).unwrap());
+        magnitude /= radix as u64;
+        if magnitude == 0 {
+            break;
+        }
+    }
+    if value < 0 {
+        digits.push('-');
+    }
+
+    helpers::get_string_object_for_value(digits.into_iter().rev().collect())
+}
+
+/// Returns the Unicode code point of a single character string
+pub fn ord(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let value = match get_argument("char", env) {
+        AllObjects::StringObj(v) => v.value,
+        v => return errors::unexpected_argument_type("a STRING", v),
+    };
+
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => helpers::get_int_object_for_value(c as i64),
+        _ => errors::single_char_error(&value),
+    }
+}
+
+/// Returns the single character string for a Unicode code point, which is the inverse of `ord`
+pub fn chr(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let code = match get_integer_argument("code", env) {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    match u32::try_from(code).ok().and_then(char::from_u32) {
+        Some(c) => helpers::get_string_object_for_value(c.to_string()),
+        None => errors::invalid_code_point(code),
+    }
+}
+
+/// Returns the string form of the given value, with the entries of hash maps ordered by their keys.
+///
+/// Unlike printing a hash map directly, the output is the same regardless of the insertion order,
+/// which makes it suitable for comparing against an expected output.
+pub fn inspect_sorted(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    match get_argument("value", env) {
+        AllObjects::Error(e) => AllObjects::Error(e),
+        v => helpers::get_string_object_for_value(helpers::inspect_sorted(&v)),
+    }
+}
+
+/// Returns the command-line arguments passed to the program as an array of strings
+pub fn args(_env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    let args = caller
+        .settings()
+        .program_args
+        .iter()
+        .map(|arg| helpers::get_string_object_for_value(arg.clone()))
+        .collect();
+
+    AllObjects::ArrayObj(ArrayObj {
+        elements: Rc::new(RefCell::new(args)),
+    })
+}
+
+/// Returns the value of the given environment variable of the process, or null if it's not set.
+///
+/// Returns an error when the evaluator runs in sandboxed mode.
+pub fn getenv(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    if caller.settings().sandboxed {
+        return errors::sandboxed_builtin("getenv");
+    }
+
+    let name = match get_argument("name", env) {
+        AllObjects::StringObj(v) => v.value,
+        v => return errors::unexpected_argument_type("a STRING", v),
+    };
+
+    match std::env::var(name.as_str()) {
+        Ok(v) => helpers::get_string_object_for_value(v),
+        Err(_) => helpers::NULL,
+    }
+}
+
+/// Splits a string into an array of its lines, without the line terminators.
+///
+/// Both `\n` and `\r\n` end a line, whether they're written as escape sequences or are actual line
+/// breaks. A trailing line terminator doesn't produce an empty line at the end.
+pub fn lines(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let value = match get_argument("value", env) {
+        AllObjects::StringObj(v) => v.value,
+        v => return errors::unexpected_argument_type("a STRING", v),
+    };
+
+    let elements = value
+        .replace("\\r\\n", "\n")
+        .replace("\\n", "\n")
+        .lines()
+        .map(|line| helpers::get_string_object_for_value(line.to_string()))
+        .collect();
+
+    AllObjects::ArrayObj(ArrayObj {
+        elements: Rc::new(RefCell::new(elements)),
+    })
+}
+
+/// Splits a CSV line into an array of its fields. Fields wrapped in double quotes can contain commas,
+/// and a doubled quote (`""`) inside them stands for a single quote.
+pub fn parse_csv_line(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let value = match get_argument("value", env) {
+        AllObjects::StringObj(v) => v.value,
+        v => return errors::unexpected_argument_type("a STRING", v),
+    };
+
+    let mut fields = vec![];
+    let mut chars = value.chars().peekable();
+    loop {
+        let mut field = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next() {
+                    Some('"') if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => return errors::csv_unterminated_quote(),
+                }
+            }
+            if let Some(c) = chars.next_if(|c| *c != ',') {
+                return errors::csv_text_after_quote(c);
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != ',') {
+                field.push(c);
+            }
+        }
+
+        fields.push(helpers::get_string_object_for_value(field));
+        // the separating comma, if there's another field
+        if chars.next().is_none() {
+            break;
+        }
+    }
+
+    AllObjects::ArrayObj(ArrayObj {
+        elements: Rc::new(RefCell::new(fields)),
+    })
+}
+
+/// Builds a string by concatenating an array of strings, in order. The elements don't need to be
+/// single characters, and an empty array gives an empty string.
+pub fn from_chars(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let array = match get_argument("array", env) {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+
+    let mut value = String::new();
+    for element in array.elements.borrow().iter() {
+        match element {
+            AllObjects::StringObj(v) => value.push_str(&v.value),
+            v => return errors::unexpected_element_type("STRING", v),
+        }
+    }
+
+    helpers::get_string_object_for_value(value)
+}
+
+/// Serializes the value into a JSON string. Integers, strings, booleans, null, arrays and hash maps
+/// with string keys are supported, with the hash map entries ordered by their keys.
+pub fn json_stringify(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let value = get_argument("value", env);
+    match json::stringify(&value) {
+        Ok(v) => helpers::get_string_object_for_value(v),
+        Err(e) => e,
+    }
+}
+
+/// Parses a JSON string into the matching objects. Only integer numbers are supported, and the
+/// error for a malformed document includes the position of the problem.
+pub fn json_parse(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    match get_argument("value", env) {
+        AllObjects::StringObj(v) => json::parse(&v.value),
+        v => errors::unexpected_argument_type("a STRING", v),
+    }
+}
+
+/// Pads the string form of the value on the left up to the given width, so that it's right aligned.
+///
+/// Spaces are used by default, but a single character string can be passed as the third argument to
+/// pad with it instead. Values which are already wider than the width are returned untruncated.
+pub fn pad_left(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    pad(env, caller, Alignment::Right)
+}
+
+/// Pads the string form of the value on the right up to the given width, so that it's left aligned.
+///
+/// Accepts the same arguments as `pad_left`.
+pub fn pad_right(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    pad(env, caller, Alignment::Left)
+}
+
+/// Pads the string form of the value on both sides up to the given width, so that it's centered. If
+/// the padding can't be split evenly, the extra character goes on the right.
+///
+/// Accepts the same arguments as `pad_left`.
+pub fn center(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    pad(env, caller, Alignment::Center)
+}
+
+/// Where the text is placed within the padded width
+enum Alignment {
+    Left,
+    Right,
+    Center,
+}
+
+fn pad_parameters() -> ParamsType {
+    ParamsType::Optional(
+        vec!["value".to_string(), "width".to_string()],
+        vec!["fill".to_string()],
+    )
+}
+
+fn pad(env: Rc<Environment>, caller: Rc<Environment>, alignment: Alignment) -> AllObjects {
+    let fill = match get_optional_argument("fill", env.clone()) {
+        None => ' ',
+        Some(AllObjects::StringObj(fill)) => {
+            let mut chars = fill.value.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => return errors::pad_fill_error(),
+            }
+        }
+        Some(v) => return errors::unexpected_argument_type("a STRING", v),
+    };
+
+    let width = match get_integer_argument("width", env.clone()) {
+        Ok(v) => v.max(0) as usize,
+        Err(e) => return e,
+    };
+
+    let text = match get_argument("value", env) {
+        AllObjects::Error(e) => return AllObjects::Error(e),
+        v => v.inspect(),
+    };
+
+    let padding = width.saturating_sub(text.chars().count());
+    let size = padding
+        .saturating_mul(fill.len_utf8())
+        .saturating_add(text.len());
+    if let Err(e) = helpers::check_allocation(size, caller.settings()) {
+        return e;
+    }
+    let (before, after) = match alignment {
+        Alignment::Left => (0, padding),
+        Alignment::Right => (padding, 0),
+        Alignment::Center => (padding / 2, padding - padding / 2),
+    };
+
+    let fill = fill.to_string();
+    let padded = fill.repeat(before) + &text + &fill.repeat(after);
+
+    helpers::get_string_object_for_value(padded)
+}
+
+/// Returns a new, empty string builder.
+///
+/// Concatenating with `+` copies both strings into a new one, so building a string of n pieces in a
+/// loop takes quadratic time. Appending to a builder grows it in place, which takes amortized linear
+/// time overall. As a rough guide, building a 400 000 character string one character at a time is
+/// about ten times faster with a builder, and the gap widens as the string gets longer.
+pub fn string_builder(_env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    AllObjects::StringBuilder(StringBuilderObj {
+        value: Rc::new(RefCell::new(String::new())),
+    })
+}
+
+/// Appends the string form of the value to the end of the builder. Strings are appended as they are.
+pub fn append(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    let builder = match get_argument("builder", env.clone()) {
+        AllObjects::StringBuilder(v) => v,
+        v => return errors::unexpected_argument_type("a STRING_BUILDER", v),
+    };
+
+    let text = match get_argument("value", env) {
+        AllObjects::Error(e) => return AllObjects::Error(e),
+        AllObjects::StringObj(v) => v.value.to_string(),
+        v => v.inspect(),
+    };
+
+    let size = builder.value.borrow().len().saturating_add(text.len());
+    if let Err(e) = helpers::check_allocation(size, caller.settings()) {
+        return e;
+    }
+
+    builder.value.borrow_mut().push_str(&text);
+    helpers::NULL
+}
+
+/// Returns the contents of the builder as a string. The builder is left as it is, so that it can be
+/// appended to further.
+pub fn build(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    match get_argument("builder", env) {
+        AllObjects::StringBuilder(v) => {
+            helpers::get_string_object_for_value(v.value.borrow().clone())
+        }
+        v => errors::unexpected_argument_type("a STRING_BUILDER", v),
+    }
+}
+
+/// Removes the leading and trailing whitespace of the string
+pub fn trim(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    trim_string(env, str::trim)
+}
+
+/// Removes the leading whitespace of the string
+pub fn trim_start(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    trim_string(env, str::trim_start)
+}
+
+/// Removes the trailing whitespace of the string
+pub fn trim_end(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    trim_string(env, str::trim_end)
+}
+
+/// Removes every leading and trailing character of the string which is present in `chars`, e.g.
+/// `trim_chars("--a-b!", "-!")` gives `"a-b"`.
+pub fn trim_chars(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let chars = match get_argument("chars", env.clone()) {
+        AllObjects::StringObj(v) => v.value.chars().collect::<Vec<_>>(),
+        v => return errors::unexpected_argument_type("a STRING", v),
+    };
+
+    trim_string(env, |value| value.trim_matches(chars.as_slice()))
+}
+
+fn trim_string(env: Rc<Environment>, trim: impl Fn(&str) -> &str) -> AllObjects {
+    match get_argument("value", env) {
+        AllObjects::StringObj(v) => {
+            helpers::get_string_object_for_value(trim(&v.value).to_string())
+        }
+        v => errors::unexpected_argument_type("a STRING", v),
+    }
+}
+
+/// Returns the key-value pairs of a hash map as an array of two element arrays, ordered by the keys
+/// in the same way as `each`.
+pub fn entries(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let map = match get_argument("map", env) {
+        AllObjects::HashMap(v) => v,
+        v => return errors::unexpected_argument_type("a hash map", v),
+    };
+
+    let pairs = helpers::get_sorted_entries(&map)
+        .into_iter()
+        .map(|(k, v)| {
+            AllObjects::ArrayObj(ArrayObj {
+                elements: Rc::new(RefCell::new(vec![k, v])),
+            })
+        })
+        .collect();
+
+    AllObjects::ArrayObj(ArrayObj {
+        elements: Rc::new(RefCell::new(pairs)),
+    })
+}
+
+/// Returns the keys of a hash map as an array, ordered by their string form. Keys with the same string
+/// form, such as `1` and `"1"`, are ordered by their type names.
+pub fn sorted_keys(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let map = match get_argument("map", env) {
+        AllObjects::HashMap(v) => v,
+        v => return errors::unexpected_argument_type("a hash map", v),
+    };
+
+    let mut keys = map.map.borrow().keys().cloned().collect::<Vec<_>>();
+    keys.sort_by_cached_key(|k| (k.inspect(), k.object_type().to_string()));
+
+    AllObjects::ArrayObj(ArrayObj {
+        elements: Rc::new(RefCell::new(keys)),
+    })
+}
+
+/// Returns a new function of one argument which calls `g` with the argument and then calls `f` with
+/// the result, so `compose(f, g)(x)` is the same as `f(g(x))`.
+pub fn compose(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let f = get_argument("f", env.clone());
+    let g = get_argument("g", env);
+
+    for callback in [&f, &g] {
+        if let Err(e) = check_callback_arity(callback, 1) {
+            return e;
+        }
+    }
+
+    AllObjects::NativeClosure(NativeClosureObj {
+        fn_name: "compose".to_string(),
+        arity: 1,
+        captured: vec![f, g],
+        func: call_composed,
+    })
+}
+
+/// Calls the functions captured by `compose` one after the other, starting from the last one
+fn call_composed(
+    captured: &[AllObjects],
+    args: Vec<AllObjects>,
+    caller: Rc<Environment>,
+) -> AllObjects {
+    let mut value = args.into_iter().next().unwrap_or(helpers::NULL);
+    for function in captured.iter().rev() {
+        value = apply_function(function.clone(), vec![value], caller.clone());
+        if value.is_error() {
+            return value;
+        }
+    }
+    value
+}
+
+/// Returns a new function with the leading arguments of the given function bound to the rest of the
+/// arguments, so `partial(add, 1)(2)` is the same as `add(1, 2)`. The new function accepts the
+/// remaining arguments.
+pub fn partial(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let mut args = get_variadic_arguments(env).into_iter();
+    let Some(function) = args.next() else {
+        return errors::incorrect_min_arg_num(1, 0);
+    };
+    let bound = args.collect::<Vec<_>>();
+
+    let param_count = match &function {
+        AllObjects::Function(f) => f.parameters.len(),
+        AllObjects::BuiltinFunction(f) => match &f.parameters {
+            ParamsType::Fixed(v) => v.len(),
+            ParamsType::Variadic | ParamsType::Minimum(_) | ParamsType::Optional(..) => {
+                return errors::partial_variadic_function()
+            }
+        },
+        AllObjects::NativeClosure(f) => f.arity,
+        v => return errors::unexpected_argument_type("a FUNCTION", v.clone()),
+    };
+
+    if bound.len() > param_count {
+        return errors::partial_arg_num(param_count, bound.len());
+    }
+
+    let arity = param_count - bound.len();
+    let mut captured = vec![function];
+    captured.extend(bound);
+
+    AllObjects::NativeClosure(NativeClosureObj {
+        fn_name: "partial".to_string(),
+        arity,
+        captured,
+        func: call_partial,
+    })
+}
+
+/// Calls the function captured by `partial` with the bound values followed by the arguments
+fn call_partial(
+    captured: &[AllObjects],
+    args: Vec<AllObjects>,
+    caller: Rc<Environment>,
+) -> AllObjects {
+    let Some((function, bound)) = captured.split_first() else {
+        return helpers::NULL;
+    };
+    let args = bound.iter().cloned().chain(args).collect();
+    apply_function(function.clone(), args, caller)
+}
+
+/// Passes the value through each of the given functions from left to right, calling each function
+/// with the result of the previous one, and returns the final result.
+pub fn pipe(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    let mut args = get_variadic_arguments(env).into_iter();
+    let Some(mut value) = args.next() else {
+        return errors::incorrect_min_arg_num(1, 0);
+    };
+
+    let functions = args.collect::<Vec<_>>();
+    for function in &functions {
+        if let Err(e) = check_callback_arity(function, 1) {
+            return e;
+        }
+    }
+
+    for function in functions {
+        value = apply_function(function, vec![value], caller.clone());
+        if value.is_error() {
+            return value;
+        }
+    }
+
+    value
+}
+
+/// Returns a deep copy of the given value.
+///
+/// Arrays and hash maps are shared when assigned to another variable, so this can be used to get an
+/// independent copy which can be mutated without affecting the original.
+pub fn copy(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    get_argument("value", env).deep_copy()
+}
+
+/// Calls the callback for each element of an array, or for each key-value pair of a hash map in
+/// the order of the keys. The return values of the callback are ignored.
+pub fn each(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    let collection = get_argument("collection", env.clone());
+    let callback = get_argument("callback", env.clone());
+
+    // the elements are collected beforehand, so that the callback is free to mutate the collection
+    let (calls, arity): (Vec<Vec<AllObjects>>, usize) = match collection {
+        AllObjects::ArrayObj(v) => {
+            let calls = v
+                .elements
+                .borrow()
+                .iter()
+                .map(|e| vec![e.clone()])
+                .collect();
+            (calls, 1)
+        }
+        AllObjects::HashMap(v) => {
+            let entries = helpers::get_sorted_entries(&v);
+            (entries.into_iter().map(|(k, v)| vec![k, v]).collect(), 2)
+        }
+        v => return errors::unexpected_argument_type("an ARRAY or a hash map", v),
+    };
+
+    if let Err(e) = check_callback_arity(&callback, arity) {
+        return e;
+    }
+
+    for args in calls {
+        let result = apply_function(callback.clone(), args, caller.clone());
+        if result.is_error() {
+            return result;
+        }
+    }
+
+    helpers::NULL
+}
+
+/// Returns the sum of an array of integers, or 0 if the array is empty
+pub fn sum(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    fold_integer_array(env, 0, i64::checked_add)
+}
+
+/// Returns the product of an array of integers, or 1 if the array is empty
+pub fn product(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    fold_integer_array(env, 1, i64::checked_mul)
+}
+
+/// Returns the largest integer, which is 2^63 - 1
+pub fn max_int(_env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    helpers::get_int_object_for_value(i64::MAX)
+}
+
+/// Returns the smallest integer, which is -2^63
+pub fn min_int(_env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    helpers::get_int_object_for_value(i64::MIN)
+}
+
+/// Returns the greatest common divisor of two integers, which is never negative. `gcd(0, 0)` is 0
+pub fn gcd(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let (a, b) = match get_integer_pair(env) {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    match i64::try_from(euclid_gcd(a.unsigned_abs(), b.unsigned_abs())) {
+        Ok(v) => helpers::get_int_object_for_value(v),
+        Err(_) => errors::integer_overflow(),
+    }
+}
+
+/// Returns the least common multiple of two integers, which is never negative. It's 0 if either of
+/// the integers is 0, and an error if the result doesn't fit in an integer
+pub fn lcm(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let (a, b) = match get_integer_pair(env) {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    if a == 0 || b == 0 {
+        return helpers::get_int_object_for_value(0);
+    }
+
+    let (a, b) = (a.unsigned_abs(), b.unsigned_abs());
+    let lcm = (a / euclid_gcd(a, b))
+        .checked_mul(b)
+        .and_then(|v| i64::try_from(v).ok());
+    match lcm {
+        Some(v) => helpers::get_int_object_for_value(v),
+        None => errors::integer_overflow(),
+    }
+}
+
+fn get_integer_pair(env: Rc<Environment>) -> Result<(i64, i64), AllObjects> {
+    let a = get_integer_argument("a", env.clone())?;
+    let b = get_integer_argument("b", env)?;
+    Ok((a, b))
+}
+
+fn euclid_gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns the number of set bits in the integer. Negative integers are counted in their 64-bit
+/// two's complement form, so `bit_count(-1)` is 64
+pub fn bit_count(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    count_bits(env, i64::count_ones)
+}
+
+/// Returns the number of zero bits before the highest set bit of the 64-bit integer, which is 64
+/// for 0
+pub fn leading_zeros(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    count_bits(env, i64::leading_zeros)
+}
+
+/// Returns the number of zero bits after the lowest set bit of the 64-bit integer, which is 64 for 0
+pub fn trailing_zeros(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    count_bits(env, i64::trailing_zeros)
+}
+
+fn count_bits(env: Rc<Environment>, count: fn(i64) -> u32) -> AllObjects {
+    match get_integer_argument("value", env) {
+        Ok(v) => helpers::get_int_object_for_value(i64::from(count(v))),
+        Err(e) => e,
+    }
+}
+
+/// Splits an array into sub-arrays of the given size. The last sub-array will be shorter,
+/// if the array cannot be split evenly
+pub fn chunk(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let array = match get_argument("array", env.clone()) {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+    let size = match get_integer_argument("size", env) {
+        Ok(v) if v > 0 => v,
+        Ok(_) => return errors::chunk_size_error(),
+        Err(e) => return e,
+    };
+
+    let size = usize::try_from(size).unwrap_or(usize::MAX);
+    let chunks = array
+        .elements
+        .borrow()
+        .chunks(size)
+        .map(|c| {
+            AllObjects::ArrayObj(ArrayObj {
+                elements: Rc::new(RefCell::new(c.to_vec())),
+            })
+        })
+        .collect();
+
+    AllObjects::ArrayObj(ArrayObj {
+        elements: Rc::new(RefCell::new(chunks)),
+    })
+}
+
+/// Returns every `step`-th element of the array, starting from the first one
+pub fn step_by(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let array = match get_argument("array", env.clone()) {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+    let step = match get_integer_argument("step", env) {
+        Ok(v) if v > 0 => v,
+        Ok(_) => return errors::step_size_error(),
+        Err(e) => return e,
+    };
+
+    let step = usize::try_from(step).unwrap_or(usize::MAX);
+    let elements = array
+        .elements
+        .borrow()
+        .iter()
+        .step_by(step)
+        .cloned()
+        .collect();
+
+    AllObjects::ArrayObj(ArrayObj {
+        elements: Rc::new(RefCell::new(elements)),
+    })
+}
+
+/// Combines two arrays into an array of two-element arrays, stopping at the end of the shorter array
+pub fn zip(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let first = match get_argument("first", env.clone()) {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+    let second = match get_argument("second", env) {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+
+    let pairs = first
+        .elements
+        .borrow()
+        .iter()
+        .cloned()
+        .zip(second.elements.borrow().iter().cloned())
+        .map(|(a, b)| {
+            AllObjects::ArrayObj(ArrayObj {
+                elements: Rc::new(RefCell::new(vec![a, b])),
+            })
+        })
+        .collect();
+
+    AllObjects::ArrayObj(ArrayObj {
+        elements: Rc::new(RefCell::new(pairs)),
+    })
+}
+
+/// Returns an array containing the value repeated `count` times.
+///
+/// Each element is a deep copy of the value, so repeated arrays and hash maps are not shared.
+pub fn repeat(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    let value = get_argument("value", env.clone());
+    let count = match get_integer_argument("count", env) {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let Ok(count) = usize::try_from(count) else {
+        return errors::repeat_count_error();
+    };
+    if let Err(e) = helpers::check_allocation(count, caller.settings()) {
+        return e;
+    }
+
+    let elements = (0..count).map(|_| value.deep_copy()).collect();
+
+    AllObjects::ArrayObj(ArrayObj {
+        elements: Rc::new(RefCell::new(elements)),
+    })
+}
+
+/// Returns a string with the value repeated `count` times, joined by the separator.
+///
+/// A count of zero or less results in an empty string.
+pub fn repeat_str(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    let value = match get_argument("value", env.clone()) {
+        AllObjects::StringObj(v) => v.value,
+        v => return errors::unexpected_argument_type("a STRING", v),
+    };
+    let count = match get_integer_argument("count", env.clone()) {
+        Ok(v) => usize::try_from(v.max(0)).unwrap_or(usize::MAX),
+        Err(e) => return e,
+    };
+    let separator = match get_argument("separator", env) {
+        AllObjects::StringObj(v) => v.value,
+        v => return errors::unexpected_argument_type("a STRING", v),
+    };
+
+    if count == 0 {
+        return helpers::get_string_object_for_value(String::new());
+    }
+
+    // every repetition after the first one is preceded by the separator
+    let rest = format!("{}{}", separator, value);
+    let Some(size) = rest
+        .len()
+        .checked_mul(count - 1)
+        .and_then(|v| v.checked_add(value.len()))
+    else {
+        return errors::integer_overflow();
+    };
+    if let Err(e) = helpers::check_allocation(size, caller.settings()) {
+        return e;
+    }
+
+    helpers::get_string_object_for_value(format!("{}{}", value, rest.repeat(count - 1)))
+}
+
+/// Returns the largest of the given values. Integers, strings and arrays can be compared
+pub fn max(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    extreme_of_arguments(env, Ordering::Greater)
+}
+
+/// Returns the smallest of the given values. Integers, strings and arrays can be compared
+pub fn min(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    extreme_of_arguments(env, Ordering::Less)
+}
+
+/// Returns the first argument which compares to all the other arguments with the wanted
+/// ordering (or equal)
+fn extreme_of_arguments(env: Rc<Environment>, wanted: Ordering) -> AllObjects {
+    let mut args = get_variadic_arguments(env).into_iter();
+    let Some(mut extreme) = args.next() else {
+        return errors::incorrect_min_arg_num(1, 0);
+    };
+
+    for arg in args {
+        match helpers::compare_objects(&arg, &extreme) {
+            Ok(ordering) if ordering == wanted => extreme = arg,
+            Ok(_) => {}
+            Err(e) => return e,
+        }
+    }
+
+    extreme
+}
+
+/// Returns the element of the array with the smallest key, as computed by the key function.
+///
+/// If several elements have the smallest key, the first one is returned.
+/// Returns null, if the array is empty
+pub fn min_by(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    extreme_by(env, caller, Ordering::Less)
+}
+
+/// Returns the element of the array with the largest key, as computed by the key function.
+///
+/// If several elements have the largest key, the first one is returned.
+/// Returns null, if the array is empty
+pub fn max_by(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    extreme_by(env, caller, Ordering::Greater)
+}
+
+/// Returns the first element of the `array` argument whose key compares to the keys of all the other
+/// elements with the wanted ordering (or equal)
+fn extreme_by(env: Rc<Environment>, caller: Rc<Environment>, wanted: Ordering) -> AllObjects {
+    let array = match get_argument("array", env.clone()) {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+    let key_fn = get_argument("key_fn", env.clone());
+    if let Err(e) = check_callback_arity(&key_fn, 1) {
+        return e;
+    }
+
+    // the elements are collected beforehand, so that the key function is free to mutate the array
+    let elements = array.elements.borrow().clone();
+    let mut extreme: Option<(AllObjects, AllObjects)> = None;
+
+    for element in elements {
+        let key = apply_function(key_fn.clone(), vec![element.clone()], caller.clone());
+        if key.is_error() {
+            return key;
+        }
+
+        let is_extreme = match &extreme {
+            Some((extreme_key, _)) => match helpers::compare_objects(&key, extreme_key) {
+                Ok(ordering) => ordering == wanted,
+                Err(e) => return e,
+            },
+            None => true,
+        };
+
+        if is_extreme {
+            extreme = Some((key, element));
+        }
+    }
+
+    match extreme {
+        Some((_, element)) => element,
+        None => helpers::NULL,
+    }
+}
+
+/// Returns the first element of the array for which the predicate is truthy,
+/// or null if there is none
+pub fn find(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    match find_first(env, caller, true) {
+        Ok(Some((_, element))) => element,
+        Ok(None) => helpers::NULL,
+        Err(e) => e,
+    }
+}
+
+/// Returns the index of the first element of the array for which the predicate is truthy,
+/// or -1 if there is none
+pub fn find_index(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    match find_first(env, caller, true) {
+        Ok(Some((index, _))) => helpers::get_int_object_for_value(index as i64),
+        Ok(None) => helpers::get_int_object_for_value(-1),
+        Err(e) => e,
+    }
+}
+
+/// Checks if the predicate is truthy for every element of the array. Stops at the first element
+/// for which it is not. Returns true, if the array is empty
+pub fn all(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    match find_first(env, caller, false) {
+        Ok(v) => helpers::get_bool_consts(v.is_none()),
+        Err(e) => e,
+    }
+}
+
+/// Checks if the predicate is truthy for any element of the array. Stops at the first element
+/// for which it is. Returns false, if the array is empty
+pub fn any(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    match find_first(env, caller, true) {
+        Ok(v) => helpers::get_bool_consts(v.is_some()),
+        Err(e) => e,
+    }
+}
+
+/// Counts the elements of the array which are equal to the value.
+///
+/// If the value is a function, it's used as a predicate and the elements for which it is truthy
+/// are counted.
+pub fn count(env: Rc<Environment>, caller: Rc<Environment>) -> AllObjects {
+    let array = match get_argument("array", env.clone()) {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+    let value = get_argument("value", env.clone());
+
+    // the elements are collected beforehand, so that the predicate is free to mutate the array
+    let elements = array.elements.borrow().clone();
+
+    let count = if value.is_function() {
+        if let Err(e) = check_callback_arity(&value, 1) {
+            return e;
+        }
+
+        let mut count = 0;
+        for element in elements {
+            let result = apply_function(value.clone(), vec![element], caller.clone());
+            if result.is_error() {
+                return result;
+            }
+            if helpers::is_truthy(&result, caller.settings()) {
+                count += 1;
+            }
+        }
+        count
+    } else {
+        elements.iter().filter(|e| **e == value).count()
+    };
+
+    helpers::get_int_object_for_value(count as i64)
+}
+
+/// Returns the index and the value of the first element of the `array` argument for which the
+/// truthiness of the `predicate` argument matches the given truthiness
+fn find_first(
+    env: Rc<Environment>,
+    caller: Rc<Environment>,
+    truthy: bool,
+) -> Result<Option<(usize, AllObjects)>, AllObjects> {
+    let array = match get_argument("array", env.clone()) {
+        AllObjects::ArrayObj(v) => v,
+        v => return Err(errors::unexpected_argument_type("an ARRAY", v)),
+    };
+    let predicate = get_argument("predicate", env.clone());
+    check_callback_arity(&predicate, 1)?;
+
+    // the elements are collected beforehand, so that the predicate is free to mutate the array
+    let elements = array.elements.borrow().clone();
+
+    for (index, element) in elements.into_iter().enumerate() {
+        let result = apply_function(predicate.clone(), vec![element.clone()], caller.clone());
+        if result.is_error() {
+            return Err(result);
+        }
+        if helpers::is_truthy(&result, caller.settings()) == truthy {
+            return Ok(Some((index, element)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Flattens nested arrays into a new array, `depth` levels deep. The depth is 1, if it is not given
+///
+/// Elements which are not arrays are kept as they are.
+pub fn flat(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+    let depth = match get_optional_argument("depth", env.clone()) {
+        None => 1,
+        Some(AllObjects::Integer(v)) if v.value >= 0 => v.value,
+        Some(AllObjects::Integer(_)) => return errors::flat_depth_error(),
+        Some(v) => return errors::unexpected_argument_type("an INTEGER", v),
+    };
+
+    let array = match get_argument("array", env) {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+
+    let mut elements = Vec::new();
+    flatten_into(&array.elements.borrow(), depth, &mut elements);
+
+    AllObjects::ArrayObj(ArrayObj {
+        elements: Rc::new(RefCell::new(elements)),
+    })
+}
+
+fn flatten_into(elements: &[AllObjects], depth: i64, out: &mut Vec<AllObjects>) {
+    for element in elements {
+        match element {
+            AllObjects::ArrayObj(v) if depth > 0 => {
+                flatten_into(&v.elements.borrow(), depth - 1, out)
+            }
+            v => out.push(v.clone()),
+        }
+    }
+}
+
+/// Folds the integer elements of the `array` argument using the given checked operation,
+/// returning an overflow error if the operation overflows
+fn fold_integer_array(
+    env: Rc<Environment>,
+    initial: i64,
+    operation: fn(i64, i64) -> Option<i64>,
+) -> AllObjects {
+    let array = match get_argument("array", env) {
+        AllObjects::ArrayObj(v) => v,
+        v => return errors::unexpected_argument_type("an ARRAY", v),
+    };
+
+    let mut result = initial;
+    for element in array.elements.borrow().iter() {
+        let value = match element {
+            AllObjects::Integer(v) => v.value,
+            v => return errors::unexpected_element_type("INTEGER", v),
+        };
+        result = match operation(result, value) {
+            Some(v) => v,
+            None => return errors::integer_overflow(),
+        };
+    }
+
+    helpers::get_int_object_for_value(result)
+}
+
+/// Returns the value of a required argument, or an error if it wasn't supplied
+fn get_argument(arg_name: &str, env: Rc<Environment>) -> AllObjects {
+    match get_optional_argument(arg_name, env) {
+        Some(v) => v,
+        None => errors::argument_not_found(arg_name),
+    }
+}
+
+/// Returns the value of an optional argument, or None if it was omitted. A null that was passed
+/// explicitly is returned as a value.
+fn get_optional_argument(arg_name: &str, env: Rc<Environment>) -> Option<AllObjects> {
+    env.get(arg_name)
+}
+
+/// Returns the arguments supplied to a variadic function in the order they were passed
+fn get_variadic_arguments(env: Rc<Environment>) -> Vec<AllObjects> {
+    (0..env.all_vars().len())
+        .map_while(|i| env.get(&format!("arg_{}", i)))
+        .collect()
+}
+
+/// Returns the underlying value of an integer argument, or an error object if the argument is of another type
+fn get_integer_argument(arg_name: &str, env: Rc<Environment>) -> Result<i64, AllObjects> {
+    match get_argument(arg_name, env) {
+        AllObjects::Integer(v) => Ok(v.value),
+        v if v.is_error() => Err(v),
+        v => Err(errors::unexpected_argument_type("an INTEGER", v)),
+    }
+}
+
+/// Checks whether the callback is a function which accepts the expected number of arguments
+fn check_callback_arity(callback: &AllObjects, expected: usize) -> Result<(), AllObjects> {
+    let actual = match callback {
+        AllObjects::Function(f) => f.parameters.len(),
+        AllObjects::BuiltinFunction(f) => match &f.parameters {
+            ParamsType::Fixed(v) => v.len(),
+            ParamsType::Variadic => return Ok(()),
+            ParamsType::Minimum(min) if expected >= *min => return Ok(()),
+            ParamsType::Minimum(min) => *min,
+            ParamsType::Optional(required, optional)
+                if (required.len()..=required.len() + optional.len()).contains(&expected) =>
+            {
+                return Ok(())
+            }
+            ParamsType::Optional(required, _) => required.len(),
+        },
+        AllObjects::NativeClosure(f) => f.arity,
+        v => return Err(errors::unexpected_argument_type("a FUNCTION", v.clone())),
+    };
+
+    if actual != expected {
+        return Err(errors::incorrect_callback_arity(expected, actual));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_argument() {
+        let env = Environment::new();
+        env.set("value".to_string(), helpers::NULL);
+
+        assert!(get_argument("value", env.clone()).is_null());
+        match get_argument("map", env.clone()) {
+            AllObjects::Error(e) => assert_eq!(e.message, "missing required argument map"),
+            _ => panic!("expected an error object"),
+        }
+        match get_integer_argument("count", env.clone()) {
+            Err(AllObjects::Error(e)) => assert_eq!(e.message, "missing required argument count"),
+            _ => panic!("expected an error object"),
+        }
+
+        // an explicit null is a supplied value, unlike an omitted argument
+        assert!(get_optional_argument("value", env.clone()).is_some_and(|v| v.is_null()));
+        assert!(get_optional_argument("fill", env).is_none());
     }
 }
@@ -1,12 +1,15 @@
-use crate::object::{AllObjects, ObjectType};
+use crate::object::{objects::ErrorKind, AllObjects};
 
 pub fn type_mismatch(left: &AllObjects, operator: &str, right: &AllObjects) -> AllObjects {
-    AllObjects::new_error(&format!(
-        "type mismatch: {} {} {}",
-        left.object_type(),
-        operator,
-        right.object_type()
-    ))
+    AllObjects::new_error(
+        ErrorKind::TypeMismatch,
+        &format!(
+            "type mismatch: {} {} {}",
+            left.object_type(),
+            operator,
+            right.object_type()
+        ),
+    )
 }
 
 pub fn unknown_operator(
@@ -15,60 +18,375 @@ pub fn unknown_operator(
     right: &AllObjects,
 ) -> AllObjects {
     if let Some(l) = left {
-        return AllObjects::new_error(&format!(
-            "unknown operator: {} {} {}",
-            l.object_type(),
-            operator,
-            right.object_type()
-        ));
+        return AllObjects::new_error(
+            ErrorKind::UnknownOperator,
+            &format!(
+                "unknown operator: {} {} {}",
+                l.object_type(),
+                operator,
+                right.object_type()
+            ),
+        );
     }
-    AllObjects::new_error(&format!(
-        "unknown operator: {}{}",
-        operator,
-        right.object_type()
-    ))
+    AllObjects::new_error(
+        ErrorKind::UnknownOperator,
+        &format!("unknown operator: {}{}", operator, right.object_type()),
+    )
+}
+
+pub fn incomparable_types(left: &AllObjects, right: &AllObjects) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::TypeMismatch,
+        &format!(
+            "cannot compare {} with {}",
+            left.object_type(),
+            right.object_type()
+        ),
+    )
 }
 
 pub fn identifier_not_found(ident: &str) -> AllObjects {
-    AllObjects::new_error(&format!("identifier not found: {}", ident))
+    AllObjects::new_error(
+        ErrorKind::UnknownIdentifier,
+        &format!("identifier not found: {}", ident),
+    )
 }
 
 pub fn incorrect_arg_num(expected: usize, actual: usize) -> AllObjects {
-    AllObjects::new_error(&format!(
-        "incorrect number of arguments supplied, expected: {}, supplied {}",
-        expected, actual
-    ))
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!(
+            "incorrect number of arguments supplied, expected: {}, supplied {}",
+            expected, actual
+        ),
+    )
+}
+
+pub fn incorrect_min_arg_num(min: usize, actual: usize) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!(
+            "incorrect number of arguments supplied, expected at least: {}, supplied {}",
+            min, actual
+        ),
+    )
+}
+
+pub fn incorrect_arg_num_range(min: usize, max: usize, actual: usize) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!(
+            "incorrect number of arguments supplied, expected: {} to {}, supplied {}",
+            min, max, actual
+        ),
+    )
 }
 
-pub fn argument_not_found(expected_arg: &str, expected_arg_type: ObjectType) -> AllObjects {
-    AllObjects::new_error(&format!(
-        "expected argument {} of type {}",
-        expected_arg, expected_arg_type
-    ))
+pub fn incorrect_callback_arity(expected: usize, actual: usize) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!(
+            "incorrect number of callback parameters, expected: {}, found {}",
+            expected, actual
+        ),
+    )
+}
+
+pub fn argument_not_found(expected_arg: &str) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!("missing required argument {}", expected_arg),
+    )
 }
 
 pub fn unexpected_argument_type(expected: &str, actual: AllObjects) -> AllObjects {
     let expected = expected.to_string();
     let actual = actual.object_type().to_string();
 
-    AllObjects::new_error(&format!(
-        "expected {} argument, but received {} {}",
-        expected,
-        a_or_an(&actual),
-        actual
-    ))
+    AllObjects::new_error(
+        ErrorKind::TypeMismatch,
+        &format!(
+            "expected {} argument, but received {} {}",
+            expected,
+            a_or_an(&actual),
+            actual
+        ),
+    )
+}
+
+pub fn unexpected_element_type(expected: &str, actual: &AllObjects) -> AllObjects {
+    let actual = actual.object_type().to_string();
+
+    AllObjects::new_error(
+        ErrorKind::TypeMismatch,
+        &format!(
+            "expected {} elements, but found {} {}",
+            expected,
+            a_or_an(&actual),
+            actual
+        ),
+    )
+}
+
+pub fn destructuring_type_mismatch(expected: &str, actual: &AllObjects) -> AllObjects {
+    let actual = actual.object_type().to_string();
+
+    AllObjects::new_error(
+        ErrorKind::TypeMismatch,
+        &format!(
+            "expected {} to destructure, but received {} {}",
+            expected,
+            a_or_an(&actual),
+            actual
+        ),
+    )
+}
+
+pub fn destructuring_too_short(expected: usize, actual: usize) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::IndexOutOfRange,
+        &format!(
+            "cannot destructure {} names from an array of {} elements",
+            expected, actual
+        ),
+    )
+}
+
+pub fn spread_type_mismatch(actual: &AllObjects) -> AllObjects {
+    let actual = actual.object_type().to_string();
+
+    AllObjects::new_error(
+        ErrorKind::TypeMismatch,
+        &format!(
+            "expected an ARRAY to spread, but received {} {}",
+            a_or_an(&actual),
+            actual
+        ),
+    )
+}
+
+pub fn misplaced_spread() -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        "spread is only allowed in call arguments and array literals",
+    )
+}
+
+pub fn unhashable_key(key: &AllObjects) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::TypeMismatch,
+        &format!(
+            "{} {} can't be used as a hash map key, since it can be mutated",
+            a_or_an(&key.object_type().to_string()),
+            key.object_type()
+        ),
+    )
 }
 
 pub fn indexing_error() -> AllObjects {
-    AllObjects::new_error("list index out of range")
+    AllObjects::new_error(ErrorKind::IndexOutOfRange, "list index out of range")
 }
 
 pub fn incorrect_index_argument() -> AllObjects {
-    AllObjects::new_error("list index argument should be a positive integer")
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        "list index argument should be a positive integer",
+    )
+}
+
+pub fn return_outside_function() -> AllObjects {
+    AllObjects::new_error(ErrorKind::InvalidControlFlow, "return outside of function")
+}
+
+pub fn break_outside_loop() -> AllObjects {
+    AllObjects::new_error(ErrorKind::InvalidControlFlow, "break outside of a loop")
+}
+
+pub fn division_by_zero() -> AllObjects {
+    AllObjects::new_error(ErrorKind::DivisionByZero, "division by zero")
+}
+
+pub fn integer_overflow() -> AllObjects {
+    AllObjects::new_error(ErrorKind::Overflow, "integer overflow")
+}
+
+pub fn chunk_size_error() -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        "chunk size should be a positive integer",
+    )
+}
+
+pub fn step_size_error() -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        "step size should be a positive integer",
+    )
+}
+
+pub fn flat_depth_error() -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        "flat depth should not be negative",
+    )
+}
+
+pub fn repeat_count_error() -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        "repeat count should not be negative",
+    )
+}
+
+pub fn radix_error(radix: i64) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!("radix should be between 2 and 36, but received {}", radix),
+    )
+}
+
+pub fn invalid_integer_literal(value: &str, radix: i64) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!(
+            "could not parse \"{}\" as an integer in radix {}",
+            value, radix
+        ),
+    )
+}
+
+pub fn single_char_error(value: &str) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!("expected a single character, but received \"{}\"", value),
+    )
+}
+
+pub fn invalid_code_point(code: i64) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!("{} is not a valid code point", code),
+    )
+}
+
+pub fn exit_code_error(code: i64) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!(
+            "exit code should be between 0 and 255, but received {}",
+            code
+        ),
+    )
+}
+
+pub fn sandboxed_builtin(fn_name: &str) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::PermissionDenied,
+        &format!("{} is not available in sandboxed mode", fn_name),
+    )
+}
+
+pub fn output_limit_exceeded() -> AllObjects {
+    AllObjects::new_error(ErrorKind::LimitExceeded, "output limit exceeded")
+}
+
+pub fn allocation_limit_exceeded() -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::LimitExceeded,
+        "requested allocation exceeds limit",
+    )
+}
+
+pub fn execution_budget_exhausted() -> AllObjects {
+    AllObjects::new_error(ErrorKind::LimitExceeded, "execution budget exhausted")
+}
+
+pub fn unwrap_err(message: &AllObjects) -> AllObjects {
+    let message = match message {
+        AllObjects::StringObj(v) => v.value.to_string(),
+        v => v.to_string(),
+    };
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!("called unwrap on an err: {}", message),
+    )
+}
+
+pub fn pad_fill_error() -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        "pad fill should be a single character",
+    )
+}
+
+pub fn partial_arg_num(params: usize, bound: usize) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!(
+            "partial received {} arguments for a function with {} parameters",
+            bound, params
+        ),
+    )
+}
+
+pub fn partial_variadic_function() -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        "partial expects a function with a fixed number of parameters",
+    )
+}
+
+pub fn csv_unterminated_quote() -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        "unterminated quoted field in the CSV line",
+    )
+}
+
+pub fn csv_text_after_quote(c: char) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!(
+            "expected a comma after a quoted CSV field, but found '{}'",
+            c
+        ),
+    )
+}
+
+pub fn json_unsupported_value(actual: &AllObjects) -> AllObjects {
+    let actual = actual.object_type().to_string();
+
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!("cannot serialize {} {} to JSON", a_or_an(&actual), actual),
+    )
+}
+
+pub fn json_non_string_key(actual: &AllObjects) -> AllObjects {
+    let actual = actual.object_type().to_string();
+
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!(
+            "JSON object keys should be strings, but found {} {}",
+            a_or_an(&actual),
+            actual
+        ),
+    )
+}
+
+pub fn json_parse_error(message: &str, position: usize) -> AllObjects {
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        &format!("invalid JSON at position {}: {}", position, message),
+    )
 }
 
 pub fn sleep_arg_error() -> AllObjects {
-    AllObjects::new_error("sleep only accept positive integers")
+    AllObjects::new_error(
+        ErrorKind::InvalidArgument,
+        "sleep only accept positive integers",
+    )
 }
 
 const A: &str = "a";
@@ -5,8 +5,10 @@ use super::helpers::{self, *};
 use crate::object::objects::BuiltinFunctionObj;
 use crate::object::objects::FunctionObj;
 use crate::object::objects::HashMapObj;
+use crate::object::objects::NativeClosureObj;
 use crate::{
     ast::{expressions::*, statements::*, AllNodes},
+    lexer::keywords,
     object::{
         environment::Environment,
         objects::{ArrayObj, Boolean, Integer, ParamsType, StringObj},
@@ -14,11 +16,19 @@ use crate::{
     },
 };
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::rc::Rc;
 
 /// eval takes in any type of node and applies the appropriate evaluation logic
 pub fn eval(node: AllNodes, env: Rc<Environment>) -> Option<AllObjects> {
+    if let Some(count) = &env.settings().evaluation_count {
+        count.set(count.get() + 1);
+    }
+    if let Err(e) = take_step(&env) {
+        return Some(e);
+    }
+
     match node {
         AllNodes::Program(p) => eval_program(p.statements, env),
         AllNodes::Statements(s) => eval_statement(s, env),
@@ -26,18 +36,31 @@ pub fn eval(node: AllNodes, env: Rc<Environment>) -> Option<AllObjects> {
     }
 }
 
+/// Takes a step from the execution budget, if there is one. An error is returned once the budget is exhausted.
+fn take_step(env: &Environment) -> Result<(), AllObjects> {
+    match &env.settings().execution_budget {
+        Some(budget) if budget.get() == 0 => Err(errors::execution_budget_exhausted()),
+        Some(budget) => {
+            budget.set(budget.get() - 1);
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
+
 fn eval_program(stmts: Vec<AllStatements>, env: Rc<Environment>) -> Option<AllObjects> {
     let mut result = None;
 
     for stmt in stmts {
         result = eval(AllNodes::Statements(stmt), env.clone());
 
-        // if the value is a ReturnValue then return early with its underlying value.
-        // if the value is an error, return early with the error
+        // a ReturnValue or a BreakValue cannot reach the program scope, as there is no enclosing
+        // function or loop. if the value is an error, return early with the error
         match result {
             Some(v) => match v {
-                AllObjects::ReturnValue(r_val) => return Some(*r_val),
-                AllObjects::Error(_) => return Some(v),
+                AllObjects::ReturnValue(_) => return Some(errors::return_outside_function()),
+                AllObjects::BreakValue(_) => return Some(errors::break_outside_loop()),
+                v if v.is_error() => return Some(v),
                 _ => result = Some(v),
             },
             None => {
@@ -50,12 +73,21 @@ fn eval_program(stmts: Vec<AllStatements>, env: Rc<Environment>) -> Option<AllOb
 }
 
 fn eval_statement(stmt: AllStatements, env: Rc<Environment>) -> Option<AllObjects> {
+    if let Some(hook) = &env.settings().statement_hook {
+        if let Ok(mut hook) = hook.try_borrow_mut() {
+            let token = stmt.token();
+            hook(token.line, token.column, &env);
+        }
+    }
+
     match stmt {
         AllStatements::Let(stmt) => eval_let_statement(stmt, env),
         AllStatements::Return(stmt) => eval_return_statement(stmt, env),
         AllStatements::Expression(stmt) => eval_expression(*stmt.expression?, env),
         AllStatements::Block(block) => eval_block_statement(block, env),
         AllStatements::While(stmt) => eval_while_statement(stmt, env),
+        AllStatements::Loop(stmt) => eval_loop_statement(stmt, env),
+        AllStatements::Break(stmt) => eval_break_statement(stmt, env),
     }
 }
 
@@ -64,10 +96,50 @@ fn eval_let_statement(stmt: LetStatement, env: Rc<Environment>) -> Option<AllObj
     if value.is_error() {
         return Some(value);
     }
-    Some(env.set(stmt.name.value, value))
+    let value = copy_if_required(value, &env);
+
+    match stmt.target {
+        LetTarget::Identifier(name) => Some(env.set(name.value, value)),
+        LetTarget::Array(names) => {
+            let AllObjects::ArrayObj(ref array) = value else {
+                return Some(errors::destructuring_type_mismatch("an ARRAY", &value));
+            };
+
+            // elements past the destructured names are ignored
+            let elements = array.elements.borrow();
+            if elements.len() < names.len() {
+                return Some(errors::destructuring_too_short(names.len(), elements.len()));
+            }
+
+            for (name, element) in names.into_iter().zip(elements.iter()) {
+                env.set(name.value, element.clone());
+            }
+
+            drop(elements);
+            Some(value)
+        }
+        LetTarget::HashMap(names) => {
+            let AllObjects::HashMap(ref map) = value else {
+                return Some(errors::destructuring_type_mismatch("a HASH_MAP", &value));
+            };
+
+            // like indexing, names which are not keys of the hash map are bound to null
+            for name in names {
+                let key = get_string_object_for_value(name.value.clone());
+                env.set(name.value, get_hash_map_value(map, &key));
+            }
+
+            Some(value)
+        }
+    }
 }
 
 fn eval_block_statement(block: BlockStatement, env: Rc<Environment>) -> Option<AllObjects> {
+    // empty blocks take a step as well, so that loops like `loop {}` are bounded by the budget
+    if let Err(e) = take_step(&env) {
+        return Some(e);
+    }
+
     let mut result = None;
 
     for stmt in block.statements {
@@ -75,7 +147,8 @@ fn eval_block_statement(block: BlockStatement, env: Rc<Environment>) -> Option<A
 
         if let Some(ref v) = result {
             match v {
-                AllObjects::ReturnValue(_) | AllObjects::Error(_) => return result,
+                AllObjects::ReturnValue(_) | AllObjects::BreakValue(_) => return result,
+                v if v.is_error() => return result,
                 _ => {}
             }
         }
@@ -100,11 +173,12 @@ fn eval_while_statement(stmt: WhileStatement, env: Rc<Environment>) -> Option<Al
 
     let new_env = Environment::new_enclosed_environment(env.clone());
 
-    while is_truthy(&condition) {
-        let result = eval_block_statement(stmt.body.clone(), new_env.clone())?;
-
-        match result {
-            AllObjects::ReturnValue(_) | AllObjects::Error(_) => return Some(result),
+    while is_truthy(&condition, env.settings()) {
+        // an empty body evaluates to nothing, which shouldn't end the loop
+        match eval_block_statement(stmt.body.clone(), new_env.clone()) {
+            Some(AllObjects::BreakValue(v)) => return Some(*v),
+            Some(result @ AllObjects::ReturnValue(_)) => return Some(result),
+            Some(v) if v.is_error() => return Some(v),
             _ => {}
         }
 
@@ -117,6 +191,34 @@ fn eval_while_statement(stmt: WhileStatement, env: Rc<Environment>) -> Option<Al
     Some(helpers::NULL)
 }
 
+/// Evaluates the body of the loop until a break statement is reached, and returns the value of the break.
+///
+/// Each iteration is evaluated in a new scope enclosed by the given environment.
+fn eval_loop_statement(stmt: LoopStatement, env: Rc<Environment>) -> Option<AllObjects> {
+    loop {
+        let new_env = Environment::new_enclosed_environment(env.clone());
+        let result = eval_block_statement(stmt.body.clone(), new_env);
+
+        match result {
+            Some(AllObjects::BreakValue(v)) => return Some(*v),
+            Some(AllObjects::ReturnValue(_)) => return result,
+            Some(v) if v.is_error() => return Some(v),
+            _ => {}
+        }
+    }
+}
+
+fn eval_break_statement(stmt: BreakStatement, env: Rc<Environment>) -> Option<AllObjects> {
+    let value = match stmt.value {
+        Some(v) => eval(AllNodes::Expressions(*v), env)?,
+        None => NULL,
+    };
+    if value.is_error() {
+        return Some(value);
+    }
+    Some(AllObjects::BreakValue(Box::new(value)))
+}
+
 fn eval_expression(exprs: AllExpressions, env: Rc<Environment>) -> Option<AllObjects> {
     match exprs {
         AllExpressions::IntegerLiteral(node) => Some(get_int_object(node)),
@@ -134,6 +236,88 @@ fn eval_expression(exprs: AllExpressions, env: Rc<Environment>) -> Option<AllObj
         AllExpressions::IndexExpression(node) => eval_index_expression(node, env),
         AllExpressions::RangeExpression(node) => eval_range_expression(node, env),
         AllExpressions::HashLiteral(node) => eval_hash_map(node, env),
+        AllExpressions::Spread(_) => Some(errors::misplaced_spread()),
+        AllExpressions::Block(node) => {
+            let new_env = Environment::new_enclosed_environment(env);
+            eval_block_statement(node.body, new_env)
+        }
+        AllExpressions::Match(node) => eval_match_expression(node, env),
+    }
+}
+
+/// Evaluates the body of the first arm matching the value in a new scope holding the names bound by
+/// its pattern. Null is returned if none of the arms match.
+fn eval_match_expression(node: MatchExpression, env: Rc<Environment>) -> Option<AllObjects> {
+    let value = eval(AllNodes::Expressions(*node.value), env.clone())?;
+    if value.is_error() {
+        return Some(value);
+    }
+
+    for arm in node.arms {
+        let arm_env = Environment::new_enclosed_environment(env.clone());
+        match match_pattern(arm.pattern, &value, &arm_env) {
+            Ok(true) => return eval(AllNodes::Expressions(*arm.body), arm_env),
+            Ok(false) => continue,
+            Err(e) => return Some(e),
+        }
+    }
+
+    Some(NULL)
+}
+
+/// Checks whether the value matches the pattern, and if so, binds the names of the pattern in the
+/// environment. An error is returned if the value of a value pattern can't be evaluated.
+fn match_pattern(
+    pattern: MatchPattern,
+    value: &AllObjects,
+    env: &Rc<Environment>,
+) -> Result<bool, AllObjects> {
+    match pattern {
+        MatchPattern::Binding(name) => {
+            env.set(name.value, value.clone());
+            Ok(true)
+        }
+        MatchPattern::Array(names) => {
+            let AllObjects::ArrayObj(array) = value else {
+                return Ok(false);
+            };
+            let elements = array.elements.borrow();
+            if elements.len() != names.len() {
+                return Ok(false);
+            }
+
+            for (name, element) in names.into_iter().zip(elements.iter()) {
+                env.set(name.value, element.clone());
+            }
+            Ok(true)
+        }
+        MatchPattern::HashMap(names) => {
+            let AllObjects::HashMap(map) = value else {
+                return Ok(false);
+            };
+            let entries = names
+                .into_iter()
+                .map(|name| {
+                    let key = get_string_object_for_value(name.value.clone());
+                    map.map.borrow().get(&key).map(|v| (name.value, v.clone()))
+                })
+                .collect::<Option<Vec<_>>>();
+
+            let Some(entries) = entries else {
+                return Ok(false);
+            };
+            for (name, value) in entries {
+                env.set(name, value);
+            }
+            Ok(true)
+        }
+        MatchPattern::Value(expr) => {
+            let expected = eval(AllNodes::Expressions(*expr), env.clone()).unwrap_or(NULL);
+            if expected.is_error() {
+                return Err(expected);
+            }
+            Ok(expected == *value)
+        }
     }
 }
 
@@ -143,6 +327,7 @@ fn eval_assignment_expression(
 ) -> Option<AllObjects> {
     let ident = node.ident;
     let evaluated = eval(AllNodes::Expressions(*node.value), env.clone())?;
+    let evaluated = copy_if_required(evaluated, &env);
 
     match env.replace(&ident.value, evaluated) {
         Some(v) => Some(v),
@@ -152,14 +337,14 @@ fn eval_assignment_expression(
 
 fn eval_prefix_expression(node: PrefixExpression, env: Rc<Environment>) -> Option<AllObjects> {
     let right = node.right?;
-    let right_evaluated = eval(AllNodes::Expressions(*right), env)?;
+    let right_evaluated = eval(AllNodes::Expressions(*right), env.clone())?;
 
     if right_evaluated.is_error() {
         return Some(right_evaluated);
     }
 
     let result = match node.operator.as_str() {
-        "!" => eval_bang_operator(right_evaluated),
+        "!" => eval_bang_operator(right_evaluated, env.settings()),
         "-" => eval_minus_operator(right_evaluated),
         _ => NULL,
     };
@@ -173,11 +358,38 @@ fn eval_infix_expression(node: InfixExpression, env: Rc<Environment>) -> Option<
         return Some(left);
     }
 
-    let right = eval(AllNodes::Expressions(*node.right?), env)?;
+    // the right operand is only evaluated when the left one is null
+    if node.operator == "??" {
+        if !left.is_null() {
+            return Some(left);
+        }
+        return eval(AllNodes::Expressions(*node.right?), env);
+    }
+
+    let right = eval(AllNodes::Expressions(*node.right?), env.clone())?;
     if right.is_error() {
         return Some(right);
     }
 
+    if node.operator == keywords::IN {
+        return Some(eval_in_operator(left, right));
+    }
+
+    if let Some(method) = get_operator_method(&left, &node.operator) {
+        return Some(apply_function(method, vec![right], env));
+    }
+
+    // string repetition mixes types, so it has to be checked before the mismatch below
+    if node.operator == "*" {
+        match (&left, &right) {
+            (AllObjects::StringObj(s), AllObjects::Integer(n))
+            | (AllObjects::Integer(n), AllObjects::StringObj(s)) => {
+                return Some(eval_string_repetition(&s.value, n.value, &env));
+            }
+            _ => {}
+        }
+    }
+
     if left.object_type() != right.object_type() {
         return Some(errors::type_mismatch(&left, &node.operator, &right));
     };
@@ -190,6 +402,9 @@ fn eval_infix_expression(node: InfixExpression, env: Rc<Environment>) -> Option<
     if left.is_string() && right.is_string() {
         return Some(eval_string_operations(left, &node.operator, right));
     }
+    if left.is_array() && right.is_array() {
+        return Some(eval_array_comparisons(left, &node.operator, right));
+    }
 
     Some(errors::unknown_operator(
         Some(&left),
@@ -198,6 +413,28 @@ fn eval_infix_expression(node: InfixExpression, env: Rc<Environment>) -> Option<
     ))
 }
 
+/// Returns the function a hash map holds under the special key of the operator, such as `"__add__"`
+/// for `+`, which lets maps define how they behave with arithmetic operators.
+fn get_operator_method(left: &AllObjects, operator: &str) -> Option<AllObjects> {
+    let AllObjects::HashMap(map) = left else {
+        return None;
+    };
+    let key = match operator {
+        "+" => "__add__",
+        "-" => "__sub__",
+        "*" => "__mul__",
+        "/" => "__div__",
+        _ => return None,
+    };
+
+    let method = map
+        .map
+        .borrow()
+        .get(&helpers::get_string_object_for_value(key.to_string()))
+        .cloned()?;
+    method.is_function().then_some(method)
+}
+
 fn eval_if_expression(expr: IfExpression, env: Rc<Environment>) -> Option<AllObjects> {
     let condition = eval(AllNodes::Expressions(*expr.condition), env.clone())?;
     if condition.is_error() {
@@ -205,7 +442,7 @@ fn eval_if_expression(expr: IfExpression, env: Rc<Environment>) -> Option<AllObj
     }
 
     let new_env = Environment::new_enclosed_environment(env);
-    if is_truthy(&condition) {
+    if is_truthy(&condition, new_env.settings()) {
         return eval_block_statement(expr.consequence, new_env);
     }
 
@@ -238,7 +475,7 @@ fn eval_call_expression(node: CallExpression, env: Rc<Environment>) -> Option<Al
         return Some(function);
     }
 
-    let mut args = eval_expressions(node.arguments, env)?;
+    let mut args = eval_expressions(node.arguments, env.clone())?;
     if args.len() == 1 && args[0].is_error() {
         return Some(args.remove(0));
     }
@@ -248,12 +485,34 @@ fn eval_call_expression(node: CallExpression, env: Rc<Environment>) -> Option<Al
     }
 
     if let AllObjects::BuiltinFunction(f) = function {
-        return eval_builtin_function_calls(f, args);
+        return eval_builtin_function_calls(f, args, env);
+    }
+
+    if let AllObjects::NativeClosure(f) = function {
+        return Some(eval_native_closure_call(f, args, env));
     }
 
     None
 }
 
+/// Calls a user defined or a builtin function with the given arguments and returns the result.
+///
+/// This is used by the builtin functions which accept callbacks.
+pub fn apply_function(
+    function: AllObjects,
+    args: Vec<AllObjects>,
+    env: Rc<Environment>,
+) -> AllObjects {
+    let result = match function {
+        AllObjects::Function(f) => eval_user_defined_function_call(f, args),
+        AllObjects::BuiltinFunction(f) => eval_builtin_function_calls(f, args, env),
+        AllObjects::NativeClosure(f) => Some(eval_native_closure_call(f, args, env)),
+        v => return errors::unexpected_argument_type("a FUNCTION", v),
+    };
+
+    result.unwrap_or(helpers::NULL)
+}
+
 fn eval_user_defined_function_call(f: FunctionObj, args: Vec<AllObjects>) -> Option<AllObjects> {
     let func_env = Environment::new_enclosed_environment(f.env);
 
@@ -270,10 +529,33 @@ fn eval_user_defined_function_call(f: FunctionObj, args: Vec<AllObjects>) -> Opt
     if let Some(AllObjects::ReturnValue(r_val)) = evaluated {
         return Some(*r_val);
     }
+    if let Some(AllObjects::BreakValue(_)) = evaluated {
+        return Some(errors::break_outside_loop());
+    }
     return evaluated;
 }
 
-fn eval_builtin_function_calls(f: BuiltinFunctionObj, args: Vec<AllObjects>) -> Option<AllObjects> {
+/// Calls the native closure with its captured values and the arguments, which have to match its arity
+fn eval_native_closure_call(
+    f: NativeClosureObj,
+    args: Vec<AllObjects>,
+    env: Rc<Environment>,
+) -> AllObjects {
+    if f.arity != args.len() {
+        return errors::incorrect_arg_num(f.arity, args.len());
+    }
+    (f.func)(&f.captured, args, env)
+}
+
+/// Binds the arguments into a new environment and calls the builtin function with it.
+///
+/// The caller's environment is passed to the builtin as well, so that builtins like `globals` can
+/// look up the variables of the calling scope, and callbacks can be called from the calling scope.
+fn eval_builtin_function_calls(
+    f: BuiltinFunctionObj,
+    args: Vec<AllObjects>,
+    env: Rc<Environment>,
+) -> Option<AllObjects> {
     let new_env = Environment::new();
 
     match f.parameters {
@@ -288,14 +570,47 @@ fn eval_builtin_function_calls(f: BuiltinFunctionObj, args: Vec<AllObjects>) ->
         ParamsType::Variadic => args.into_iter().enumerate().for_each(|(i, arg)| {
             new_env.set(format!("arg_{}", i), arg);
         }),
+        ParamsType::Minimum(min) => {
+            if args.len() < min {
+                return Some(errors::incorrect_min_arg_num(min, args.len()));
+            }
+            args.into_iter().enumerate().for_each(|(i, arg)| {
+                new_env.set(format!("arg_{}", i), arg);
+            })
+        }
+        ParamsType::Optional(required, optional) => {
+            let max = required.len() + optional.len();
+            if args.len() < required.len() || args.len() > max {
+                return Some(errors::incorrect_arg_num_range(
+                    required.len(),
+                    max,
+                    args.len(),
+                ));
+            }
+            required
+                .iter()
+                .chain(optional.iter())
+                .zip(args)
+                .for_each(|(param, arg)| {
+                    new_env.set(param.clone(), arg);
+                })
+        }
     }
 
-    return Some((f.func)(new_env));
+    return Some((f.func)(new_env, env));
 }
 
 fn eval_array_literal(node: ArrayLiteral, env: Rc<Environment>) -> Option<AllObjects> {
     let mut v = Vec::with_capacity(node.elements.len());
     for expr in node.elements {
+        if let AllExpressions::Spread(spread) = expr {
+            match eval_spread_expression(spread, env.clone())? {
+                AllObjects::ArrayObj(arr) => v.extend(arr.elements.borrow().iter().cloned()),
+                err => return Some(err),
+            }
+            continue;
+        }
+
         let evaluated = eval(AllNodes::Expressions(expr), env.clone())?;
         v.push(evaluated);
     }
@@ -307,6 +622,10 @@ fn eval_array_literal(node: ArrayLiteral, env: Rc<Environment>) -> Option<AllObj
 
 fn eval_index_expression(node: IndexExpression, env: Rc<Environment>) -> Option<AllObjects> {
     let evaluated_left = eval(AllNodes::Expressions(*node.left), env.clone())?;
+    if node.optional && evaluated_left.is_null() {
+        return Some(NULL);
+    }
+
     let evaluated_index = eval(AllNodes::Expressions(*node.index), env)?;
 
     if let AllObjects::HashMap(v) = &evaluated_left {
@@ -339,6 +658,11 @@ fn eval_index_expression(node: IndexExpression, env: Rc<Environment>) -> Option<
 
 /// Evaluate range expressions and returns a clone of the indexed slice of an array
 fn eval_range_expression(node: RangeExpression, env: Rc<Environment>) -> Option<AllObjects> {
+    let evaluated_left = eval(AllNodes::Expressions(*node.left), env.clone())?;
+    if node.optional && evaluated_left.is_null() {
+        return Some(NULL);
+    }
+
     let left = match eval(AllNodes::Expressions(*node.left_index), env.clone())? {
         AllObjects::Integer(v) => v,
         other => return Some(errors::unexpected_argument_type("an INTEGER", other)),
@@ -357,7 +681,7 @@ fn eval_range_expression(node: RangeExpression, env: Rc<Environment>) -> Option<
         return Some(errors::incorrect_index_argument());
     };
 
-    let val = match eval(AllNodes::Expressions(*node.left), env)? {
+    let val = match evaluated_left {
         AllObjects::ArrayObj(v) => get_array_index_value(v, left_index, Some(right_index)),
         AllObjects::StringObj(v) => get_string_index_value(v, left_index, Some(right_index)),
         other => {
@@ -376,6 +700,9 @@ fn eval_hash_map(node: HashLiteral, env: Rc<Environment>) -> Option<AllObjects>
 
     for pair in node.pairs {
         let key = eval(AllNodes::Expressions(pair.0), env.clone())?;
+        if let Err(e) = helpers::check_hash_key(&key) {
+            return Some(e);
+        }
         let value = eval(AllNodes::Expressions(pair.1), env.clone())?;
         map.insert(key, value);
     }
@@ -389,6 +716,14 @@ fn eval_expressions(exprs: Vec<AllExpressions>, env: Rc<Environment>) -> Option<
     let mut v = Vec::with_capacity(exprs.len());
 
     for expr in exprs {
+        if let AllExpressions::Spread(spread) = expr {
+            match eval_spread_expression(spread, env.clone())? {
+                AllObjects::ArrayObj(arr) => v.extend(arr.elements.borrow().iter().cloned()),
+                err => return Some(vec![err]),
+            }
+            continue;
+        }
+
         let evaluated = eval(AllNodes::Expressions(expr), env.clone())?;
         if evaluated.is_error() {
             return Some(vec![evaluated]);
@@ -399,9 +734,22 @@ fn eval_expressions(exprs: Vec<AllExpressions>, env: Rc<Environment>) -> Option<
     Some(v)
 }
 
+/// Evaluates the operand of a spread expression, which is either an array or an error object
+fn eval_spread_expression(node: SpreadExpression, env: Rc<Environment>) -> Option<AllObjects> {
+    let evaluated = eval(AllNodes::Expressions(*node.value), env)?;
+    match evaluated {
+        AllObjects::ArrayObj(_) => Some(evaluated),
+        v if v.is_error() => Some(v),
+        v => Some(errors::spread_type_mismatch(&v)),
+    }
+}
+
 fn eval_minus_operator(right: AllObjects) -> AllObjects {
     if let AllObjects::Integer(v) = right {
-        return AllObjects::Integer(Integer { value: -v.value });
+        return match v.value.checked_neg() {
+            Some(value) => AllObjects::Integer(Integer { value }),
+            None => errors::integer_overflow(),
+        };
     }
     errors::unknown_operator(None, "-", &right)
 }
@@ -423,9 +771,15 @@ fn eval_integer_calculations(left: AllObjects, operator: &str, right: AllObjects
         "+" => get_int_object_for_value(left_int + right_int),
         "-" => get_int_object_for_value(left_int - right_int),
         "*" => get_int_object_for_value(left_int * right_int),
-        "/" => get_int_object_for_value(left_int / right_int),
+        "/" if right_int == 0 => errors::division_by_zero(),
+        "/" => match left_int.checked_div(right_int) {
+            Some(v) => get_int_object_for_value(v),
+            None => errors::integer_overflow(),
+        },
         "<" => get_bool_consts(left_int < right_int),
         ">" => get_bool_consts(left_int > right_int),
+        "<=" => get_bool_consts(left_int <= right_int),
+        ">=" => get_bool_consts(left_int >= right_int),
         "!=" => get_bool_consts(left_int != right_int),
         "==" => get_bool_consts(left_int == right_int),
         _ => NULL,
@@ -450,6 +804,53 @@ fn eval_comparison_for_booleans(left: AllObjects, operator: &str, right: AllObje
     }
 }
 
+/// Checks whether the left value is a key of a hash map, an element of an array or a substring of a
+/// string on the right.
+fn eval_in_operator(left: AllObjects, right: AllObjects) -> AllObjects {
+    let found = match (&left, &right) {
+        (_, AllObjects::HashMap(m)) => m.map.borrow().contains_key(&left),
+        (_, AllObjects::ArrayObj(arr)) => arr.elements.borrow().contains(&left),
+        (AllObjects::StringObj(l), AllObjects::StringObj(r)) => r.value.contains(l.value.as_str()),
+        (_, AllObjects::StringObj(_)) => {
+            return errors::type_mismatch(&left, keywords::IN, &right);
+        }
+        _ => return errors::unknown_operator(Some(&left), keywords::IN, &right),
+    };
+
+    get_bool_consts(found)
+}
+
+/// Repeats the string the given number of times, for both `"x" * 3` and `3 * "x"`.
+fn eval_string_repetition(value: &str, count: i64, env: &Environment) -> AllObjects {
+    let Ok(count) = usize::try_from(count) else {
+        return errors::repeat_count_error();
+    };
+    let Some(size) = value.len().checked_mul(count) else {
+        return errors::integer_overflow();
+    };
+    if let Err(e) = check_allocation(size, env.settings()) {
+        return e;
+    }
+
+    helpers::get_string_object_for_value(value.repeat(count))
+}
+
+/// Compares two arrays lexicographically, element by element.
+fn eval_array_comparisons(left: AllObjects, operator: &str, right: AllObjects) -> AllObjects {
+    let check: fn(Ordering) -> bool = match operator {
+        "<" => Ordering::is_lt,
+        ">" => Ordering::is_gt,
+        "<=" => Ordering::is_le,
+        ">=" => Ordering::is_ge,
+        _ => return errors::unknown_operator(Some(&left), operator, &right),
+    };
+
+    match compare_objects(&left, &right) {
+        Ok(ordering) => get_bool_consts(check(ordering)),
+        Err(e) => e,
+    }
+}
+
 fn eval_string_operations(left: AllObjects, operator: &str, right: AllObjects) -> AllObjects {
     let left_val = match &left {
         AllObjects::StringObj(v) => v,
@@ -469,7 +870,7 @@ fn eval_string_operations(left: AllObjects, operator: &str, right: AllObjects) -
         "+" => AllObjects::StringObj(StringObj {
             value: Rc::new(format!("{}{}", left_val, right_val)),
         }),
-        ">" | "==" | "<" | "!=" => {
+        ">" | "==" | "<" | "!=" | ">=" | "<=" => {
             let Some(v) = eval_string_comparisons(left_val, operator, right_val) else {
               return errors::unknown_operator(Some(&left), operator, &right);
             };
@@ -487,6 +888,8 @@ fn eval_string_comparisons(
     let value = match operator {
         ">" => left > right,
         "<" => left < right,
+        ">=" => left >= right,
+        "<=" => left <= right,
         "==" => left == right,
         "!=" => left != right,
         _ => return None,
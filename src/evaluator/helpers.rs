@@ -2,13 +2,13 @@ use crate::{
     ast::expressions::{FunctionLiteral, IntegerLiteral, StringLiteral},
     object::{
         objects::{ArrayObj, Boolean, FunctionObj, HashMapObj, Integer, Null, StringObj},
-        AllObjects,
+        AllObjects, Object,
     },
-    Environment,
+    Environment, Settings,
 };
 
 use super::errors;
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, rc::Rc};
 use uuid::Uuid;
 
 // constants that can be reused without extra allocations
@@ -27,14 +27,39 @@ pub fn new_function_literal(node: FunctionLiteral, env: Rc<Environment>) -> AllO
     })
 }
 
-pub fn is_truthy(obj: &AllObjects) -> bool {
+/// Returns a deep copy of the value if the environment is configured to copy containers on assignment.
+///
+/// Otherwise the value is returned as it is, sharing the underlying storage of arrays and hash maps.
+pub fn copy_if_required(value: AllObjects, env: &Environment) -> AllObjects {
+    if env.settings().copy_on_assign {
+        return value.deep_copy();
+    }
+    value
+}
+
+/// Checks whether the object is considered true by conditions. Only `false` and `null` are falsy,
+/// unless strict truthiness is enabled in the settings.
+pub fn is_truthy(obj: &AllObjects, settings: &Settings) -> bool {
     match obj {
         AllObjects::Boolean(v) => v.value,
         AllObjects::Null(_) => false,
+        AllObjects::Integer(v) if settings.strict_truthiness => v.value != 0,
+        AllObjects::StringObj(v) if settings.strict_truthiness => !v.value.is_empty(),
+        AllObjects::ArrayObj(v) if settings.strict_truthiness => !v.elements.borrow().is_empty(),
+        AllObjects::HashMap(v) if settings.strict_truthiness => !v.map.borrow().is_empty(),
         _ => true,
     }
 }
 
+/// Checks the size of a new array or string, in elements or bytes, against the allocation limit in the
+/// settings, if there is one.
+pub fn check_allocation(size: usize, settings: &Settings) -> Result<(), AllObjects> {
+    match settings.allocation_limit {
+        Some(limit) if size > limit => Err(errors::allocation_limit_exceeded()),
+        _ => Ok(()),
+    }
+}
+
 pub fn get_bool_consts(val: bool) -> AllObjects {
     if val {
         return TRUE;
@@ -56,13 +81,14 @@ pub fn get_string_object(node: StringLiteral) -> AllObjects {
     })
 }
 
-pub fn eval_bang_operator(right: AllObjects) -> AllObjects {
-    match right {
-        TRUE => FALSE,
-        FALSE => TRUE,
-        NULL => TRUE,
-        _ => FALSE,
-    }
+pub fn get_string_object_for_value(value: String) -> AllObjects {
+    AllObjects::StringObj(StringObj {
+        value: Rc::new(value),
+    })
+}
+
+pub fn eval_bang_operator(right: AllObjects, settings: &Settings) -> AllObjects {
+    get_bool_consts(!is_truthy(&right, settings))
 }
 
 pub fn get_array_index_value(
@@ -95,11 +121,12 @@ pub fn get_string_index_value(
     right_index: Option<usize>,
 ) -> AllObjects {
     if let Some(right) = right_index {
-        let Some(str_slice) = str.value.get(left_index..right) else {
+        if left_index > right || right > str.value.chars().count() {
             return errors::indexing_error();
-        };
+        }
+        let str_slice = str.value.chars().skip(left_index).take(right - left_index);
         return AllObjects::StringObj(StringObj {
-            value: Rc::new(str_slice.to_string()),
+            value: Rc::new(str_slice.collect()),
         });
     }
 
@@ -113,9 +140,93 @@ pub fn get_string_index_value(
     })
 }
 
+/// Checks whether the value can be used as a hash map key. Arrays, hash maps and string builders are
+/// rejected, as mutating them after insertion would change their hash and leave the map unable to
+/// find them.
+pub fn check_hash_key(key: &AllObjects) -> Result<(), AllObjects> {
+    match key {
+        AllObjects::ArrayObj(_) | AllObjects::HashMap(_) | AllObjects::StringBuilder(_) => {
+            Err(errors::unhashable_key(key))
+        }
+        _ => Ok(()),
+    }
+}
+
 pub fn get_hash_map_value(m: &HashMapObj, key: &AllObjects) -> AllObjects {
     if let Some(v) = m.map.borrow().get(key) {
         return v.clone();
     }
     NULL
 }
+
+/// Returns the ordering between two objects.
+///
+/// The ordering is the one defined by `PartialOrd` for objects. An error object is returned, if the compared
+/// values can't be ordered against each other.
+pub fn compare_objects(left: &AllObjects, right: &AllObjects) -> Result<Ordering, AllObjects> {
+    left.partial_cmp(right)
+        .ok_or_else(|| errors::incomparable_types(left, right))
+}
+
+/// Returns the entries of the hash map ordered by their keys.
+///
+/// Keys of the same type are ordered by their values, while keys of different types are ordered by their type names.
+pub fn get_sorted_entries(m: &HashMapObj) -> Vec<(AllObjects, AllObjects)> {
+    let mut entries = m
+        .map
+        .borrow()
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect::<Vec<_>>();
+
+    entries.sort_by(|(a, _), (b, _)| {
+        compare_objects(a, b).unwrap_or_else(|_| {
+            let a = (a.object_type().to_string(), a.inspect());
+            let b = (b.object_type().to_string(), b.inspect());
+            a.cmp(&b)
+        })
+    });
+
+    entries
+}
+
+/// Returns the string form of the object, same as `inspect`, except that hash map entries at any depth
+/// are ordered by the string form of their keys, so the output doesn't depend on the insertion order.
+pub fn inspect_sorted(obj: &AllObjects) -> String {
+    match obj {
+        AllObjects::StringObj(_) => obj.inspect(),
+        v => repr_sorted(v),
+    }
+}
+
+/// Returns the `repr` form of the object, with hash map entries at any depth ordered by key
+fn repr_sorted(obj: &AllObjects) -> String {
+    match obj {
+        AllObjects::ArrayObj(arr) => {
+            let elements = arr
+                .elements
+                .borrow()
+                .iter()
+                .map(repr_sorted)
+                .collect::<Vec<String>>();
+            format!("[{}]", elements.join(", "))
+        }
+        AllObjects::HashMap(m) => {
+            let mut entries = m
+                .map
+                .borrow()
+                .iter()
+                .map(|(k, v)| (repr_sorted(k), repr_sorted(v)))
+                .collect::<Vec<_>>();
+            entries.sort();
+
+            let out = entries
+                .iter()
+                .map(|(k, v)| format!("{}:{}", k, v))
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!("{{ {} }}", out)
+        }
+        v => v.repr(),
+    }
+}
@@ -0,0 +1,400 @@
+use super::{errors, helpers};
+use crate::object::{
+    objects::{ArrayObj, HashMapObj},
+    AllObjects, Object,
+};
+use std::{cell::RefCell, collections::HashMap, fmt::Write, rc::Rc};
+
+/// Serializes the object into a JSON string. Hash map entries are written in key order, so the
+/// output doesn't depend on the insertion order.
+///
+/// Functions can't be serialized and hash maps can only have string keys, the error object is
+/// returned as the error otherwise.
+pub fn stringify(obj: &AllObjects) -> Result<String, AllObjects> {
+    let mut out = String::new();
+    write_value(obj, &mut out)?;
+    Ok(out)
+}
+
+fn write_value(obj: &AllObjects, out: &mut String) -> Result<(), AllObjects> {
+    match obj {
+        AllObjects::Integer(_) | AllObjects::Boolean(_) | AllObjects::Null(_) => {
+            out.push_str(&obj.inspect());
+        }
+        AllObjects::StringObj(v) => write_string(&v.value, out),
+        AllObjects::ArrayObj(arr) => {
+            out.push('[');
+            for (i, element) in arr.elements.borrow().iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(element, out)?;
+            }
+            out.push(']');
+        }
+        AllObjects::HashMap(m) => {
+            out.push('{');
+            for (i, (key, value)) in helpers::get_sorted_entries(m).iter().enumerate() {
+                let AllObjects::StringObj(key) = key else {
+                    return Err(errors::json_non_string_key(key));
+                };
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(&key.value, out);
+                out.push(':');
+                write_value(value, out)?;
+            }
+            out.push('}');
+        }
+        v => return Err(errors::json_unsupported_value(v)),
+    }
+
+    Ok(())
+}
+
+fn write_string(value: &str, out: &mut String) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// The maximum number of arrays and objects that can be nested inside each other. The parser is
+/// recursive, so deeper documents are rejected instead of overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+/// Parses a JSON document into objects. Numbers become integers, since there are no floats, and
+/// objects become hash maps with string keys.
+///
+/// Malformed documents return an error object with the character position of the problem.
+pub fn parse(input: &str) -> AllObjects {
+    let mut parser = JsonParser {
+        chars: input.chars().collect(),
+        position: 0,
+        depth: 0,
+    };
+
+    let value = match parser.parse_value() {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    parser.skip_whitespace();
+    if parser.position < parser.chars.len() {
+        return parser.error("unexpected characters after the document");
+    }
+
+    value
+}
+
+struct JsonParser {
+    chars: Vec<char>,
+    position: usize,
+    /// the number of arrays and objects enclosing the current position
+    depth: usize,
+}
+
+impl JsonParser {
+    fn parse_value(&mut self) -> Result<AllObjects, AllObjects> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.parse_nested(Self::parse_object),
+            Some('[') => self.parse_nested(Self::parse_array),
+            Some('"') => Ok(helpers::get_string_object_for_value(self.parse_string()?)),
+            Some('-' | '0'..='9') => self.parse_number(),
+            Some('t') => self.parse_literal("true", helpers::get_bool_consts(true)),
+            Some('f') => self.parse_literal("false", helpers::get_bool_consts(false)),
+            Some('n') => self.parse_literal("null", helpers::NULL),
+            Some(c) => Err(self.error(&format!("unexpected character '{}'", c))),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    /// Parses an array or an object with the given function, unless it would be nested too deeply
+    fn parse_nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<AllObjects, AllObjects>,
+    ) -> Result<AllObjects, AllObjects> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    // JSON object keys are always strings
+    #[allow(clippy::mutable_key_type)]
+    fn parse_object(&mut self) -> Result<AllObjects, AllObjects> {
+        self.position += 1; // consume {
+        let mut map = HashMap::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+        } else {
+            loop {
+                self.skip_whitespace();
+                if self.peek() != Some('"') {
+                    return Err(self.error("expected a string key"));
+                }
+                let key = helpers::get_string_object_for_value(self.parse_string()?);
+
+                self.skip_whitespace();
+                self.expect(':')?;
+                let value = self.parse_value()?;
+                map.insert(key, value);
+
+                if self.expect_separator('}')? {
+                    break;
+                }
+            }
+        }
+
+        Ok(AllObjects::HashMap(HashMapObj {
+            map: Rc::new(RefCell::new(map)),
+        }))
+    }
+
+    fn parse_array(&mut self) -> Result<AllObjects, AllObjects> {
+        self.position += 1; // consume [
+        let mut elements = vec![];
+
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+        } else {
+            loop {
+                elements.push(self.parse_value()?);
+                if self.expect_separator(']')? {
+                    break;
+                }
+            }
+        }
+
+        Ok(AllObjects::ArrayObj(ArrayObj {
+            elements: Rc::new(RefCell::new(elements)),
+        }))
+    }
+
+    fn parse_string(&mut self) -> Result<String, AllObjects> {
+        self.position += 1; // consume the opening quote
+        let mut value = String::new();
+
+        loop {
+            let Some(c) = self.next() else {
+                return Err(self.error("unterminated string"));
+            };
+            match c {
+                '"' => return Ok(value),
+                '\\' => {
+                    let escaped = match self.next() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.parse_unicode_escape()?,
+                        _ => return Err(self.error("invalid escape sequence")),
+                    };
+                    value.push(escaped);
+                }
+                c if c.is_control() => return Err(self.error("unescaped control character")),
+                c => value.push(c),
+            }
+        }
+    }
+
+    /// Parses the hex digits of a `\uXXXX` escape, along with the low half of a surrogate pair
+    fn parse_unicode_escape(&mut self) -> Result<char, AllObjects> {
+        let high = self.parse_hex_code()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("invalid unicode escape"));
+        }
+
+        if self.next() != Some('\\') || self.next() != Some('u') {
+            return Err(self.error("expected the low half of a surrogate pair"));
+        }
+        let low = self.parse_hex_code()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("invalid low surrogate"));
+        }
+
+        let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn parse_hex_code(&mut self) -> Result<u32, AllObjects> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let Some(digit) = self.next().and_then(|c| c.to_digit(16)) else {
+                return Err(self.error("invalid unicode escape"));
+            };
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+
+    fn parse_number(&mut self) -> Result<AllObjects, AllObjects> {
+        let start = self.position;
+        if self.peek() == Some('-') {
+            self.position += 1;
+        }
+
+        let digits_start = self.position;
+        while matches!(self.peek(), Some('0'..='9')) {
+            self.position += 1;
+        }
+        let digits = self.position - digits_start;
+        if digits == 0 {
+            return Err(self.error("expected a digit"));
+        }
+        if digits > 1 && self.chars[digits_start] == '0' {
+            return Err(self.error_at(digits_start, "numbers can't have leading zeros"));
+        }
+        if matches!(self.peek(), Some('.' | 'e' | 'E')) {
+            return Err(self.error("only integer numbers are supported"));
+        }
+
+        let literal = self.chars[start..self.position].iter().collect::<String>();
+        match literal.parse::<i64>() {
+            Ok(v) => Ok(helpers::get_int_object_for_value(v)),
+            Err(_) => Err(self.error_at(start, "number is out of the integer range")),
+        }
+    }
+
+    fn parse_literal(
+        &mut self,
+        literal: &str,
+        value: AllObjects,
+    ) -> Result<AllObjects, AllObjects> {
+        let start = self.position;
+        for expected in literal.chars() {
+            if self.next() != Some(expected) {
+                return Err(self.error_at(start, "unexpected literal"));
+            }
+        }
+        Ok(value)
+    }
+
+    /// Consumes a comma or the closing character after an element, returning whether it was the
+    /// closing one
+    fn expect_separator(&mut self, closing: char) -> Result<bool, AllObjects> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(',') => Ok(false),
+            Some(c) if c == closing => Ok(true),
+            _ => {
+                self.position -= 1;
+                Err(self.error(&format!("expected ',' or '{}'", closing)))
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), AllObjects> {
+        if self.peek() != Some(expected) {
+            return Err(self.error(&format!("expected '{}'", expected)));
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    fn error(&self, message: &str) -> AllObjects {
+        self.error_at(self.position, message)
+    }
+
+    fn error_at(&self, position: usize, message: &str) -> AllObjects {
+        errors::json_parse_error(message, position.min(self.chars.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_string_escapes() {
+        let test_cases = [
+            (r#""a\"b\\c\/d""#, "a\"b\\c/d"),
+            (r#""tab\tnew\nline""#, "tab\tnew\nline"),
+            (r#""\u00e9\ud83d\ude00""#, "\u{e9}\u{1f600}"),
+        ];
+        for tc in test_cases {
+            let AllObjects::StringObj(v) = parse(tc.0) else {
+                panic!("expected a string object for {}", tc.0);
+            };
+            assert_eq!(*v.value, tc.1);
+        }
+
+        let test_cases = [
+            (r#""abc"#, "invalid JSON at position 4: unterminated string"),
+            (
+                r#""\x""#,
+                "invalid JSON at position 3: invalid escape sequence",
+            ),
+            (
+                r#""\ud83d""#,
+                "invalid JSON at position 8: expected the low half of a surrogate pair",
+            ),
+        ];
+        for tc in test_cases {
+            let AllObjects::Error(e) = parse(tc.0) else {
+                panic!("expected an error for {}", tc.0);
+            };
+            assert_eq!(e.message, tc.1);
+        }
+    }
+
+    #[test]
+    fn test_parse_nesting_depth() {
+        let nested = format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+        assert!(matches!(parse(&nested), AllObjects::ArrayObj(_)));
+
+        let nested = format!("{}1{}", r#"{"a":"#.repeat(MAX_DEPTH), "}".repeat(MAX_DEPTH));
+        assert!(matches!(parse(&nested), AllObjects::HashMap(_)));
+
+        let test_cases = [
+            "[".repeat(200000),
+            format!("{}{}", "[".repeat(MAX_DEPTH + 1), "]".repeat(MAX_DEPTH + 1)),
+            format!("{}[{{}}]", "[".repeat(MAX_DEPTH - 1)),
+        ];
+        for tc in test_cases {
+            let AllObjects::Error(e) = parse(&tc) else {
+                panic!("expected an error for a document nested too deeply");
+            };
+            assert_eq!(e.message, "invalid JSON at position 128: nesting too deep");
+        }
+    }
+}
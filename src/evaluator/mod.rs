@@ -2,6 +2,7 @@ mod builtins;
 mod errors;
 mod eval;
 mod helpers;
+mod json;
 
 pub use eval::eval;
 
@@ -11,6 +12,7 @@ mod tests {
     use crate::object::AllObjects;
     use std::io;
     use std::sync::Arc;
+    use std::time::{Duration, Instant};
 
     #[test]
     fn test_eval_integer_expression() {
@@ -39,6 +41,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_minus_operator_overflow() {
+        let evaluated = helper_test_eval("-(-9223372036854775807 - 1)");
+        helper_test_error(evaluated, "integer overflow");
+
+        let evaluated = helper_test_eval("-(-9223372036854775807)");
+        helper_test_integer_obj(evaluated, i64::MAX);
+    }
+
     #[test]
     fn test_eval_boolean_expression() {
         let test_cases = [
@@ -52,6 +63,10 @@ mod tests {
             ("1 != 1", false),
             ("1 == 2", false),
             ("1 != 2", true),
+            ("1 <= 1", true),
+            ("2 <= 1", false),
+            ("1 >= 1", true),
+            ("1 >= 2", false),
             ("true == true", true),
             ("false == false", true),
             ("true == false", false),
@@ -64,6 +79,10 @@ mod tests {
             (r#" "foobar" == "foobar" "#, true),
             (r#" "foo" != "baz" "#, true),
             (r#" "x" < "y" "#, true),
+            (r#" "a" <= "a" "#, true),
+            (r#" "b" <= "a" "#, false),
+            (r#" "b" >= "a" "#, true),
+            (r#" "a" >= "b" "#, false),
             (
                 r#" "a slightly long text" == "not so slightly long text" "#,
                 false,
@@ -119,6 +138,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_when_expressions() {
+        use crate::object::Object;
+
+        helper_test_integer_obj(helper_test_eval("when (1 < 2) { 10 }"), 10);
+        helper_test_null(helper_test_eval("when (1 > 2) { 10 }"));
+        helper_test_null(helper_test_eval("when (null) { 10 }"));
+
+        // the value is only evaluated when the condition is truthy
+        let input = r#"
+            let log = [];
+            let x = when (false) { push(log, "skipped"); 1 };
+            let y = when (true) { push(log, "ran"); 2 };
+            [x, y, log]
+        "#;
+        let evaluated = helper_test_eval(input).expect(EXPECTED_OBJECT);
+        assert_eq!(evaluated.inspect(), r#"[null, 2, ["ran"]]"#);
+    }
+
     #[test]
     fn test_if_expression_scope() {
         let input = "if (1 < 2) { 
@@ -142,17 +180,20 @@ mod tests {
     #[test]
     fn test_return_statement() {
         let test_cases = [
-            ("return 10;", 10),
-            ("return 10; 9;", 10),
-            ("return 2 * 5; 9;", 10),
-            ("9; return 2 * 5; 9;", 10),
-            ("9; return 2 * 5; 9;", 10),
+            ("fn() { return 10; }()", 10),
+            ("fn() { return 10; 9; }()", 10),
+            ("fn() { return 2 * 5; 9; }()", 10),
+            ("fn() { 9; return 2 * 5; 9; }()", 10),
+            ("let f = fn() { 9; return 2 * 5; 9; }; f() + 1;", 11),
             (
-                "if (10 > 1) {
+                "fn() {
                 if (10 > 1) {
-                return 10;
-              }
-             return 1;",
+                  if (10 > 1) {
+                    return 10;
+                  }
+                  return 1;
+                }
+              }()",
                 10,
             ),
         ];
@@ -163,6 +204,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_return_outside_function() {
+        let test_cases = [
+            "return 10;",
+            "9; return 2 * 5; 9;",
+            "if (true) { return 1; }",
+        ];
+
+        for tc in test_cases {
+            let evaluated = helper_test_eval(tc);
+            helper_test_error(evaluated, "return outside of function");
+        }
+    }
+
     #[test]
     fn test_error_handling() {
         let test_cases = [
@@ -223,8 +278,8 @@ mod tests {
     fn test_string_literals() {
         let test_cases = [
             ("let a = \"foobar\";a;", "foobar"),
-            ("return \"baz\"", "baz"),
-            ("let x = \"iron\"; return x;", "iron"),
+            ("fn() { return \"baz\" }()", "baz"),
+            ("let x = \"iron\"; fn() { return x; }()", "iron"),
         ];
 
         for tc in test_cases {
@@ -245,6 +300,43 @@ mod tests {
         assert_eq!(v.body.to_string(), "(x + 2)");
     }
 
+    #[test]
+    fn test_function_object_inspect() {
+        use crate::object::Object;
+
+        let input = "
+            let f = fn(x) {
+                if (x > 1) { if (x > 2) { return 3; } else { x } }
+                0
+            };
+            f;
+        ";
+        let evaluated = helper_test_eval(input).expect(EXPECTED_FUNCTION);
+        let expected = "fn(x) {
+    if (x > 1) {
+        if (x > 2) {
+            return 3;
+        } else {
+            x;
+        };
+    };
+    0;
+}";
+        assert_eq!(evaluated.inspect(), expected);
+
+        let input = "fn() {}";
+        let evaluated = helper_test_eval(input).expect(EXPECTED_FUNCTION);
+        assert_eq!(evaluated.inspect(), "fn() {}");
+
+        // multi-line string literals are shown as they were written
+        let input = "fn() { let s = \"a\nb\"; s }";
+        let evaluated = helper_test_eval(input).expect(EXPECTED_FUNCTION);
+        assert_eq!(
+            evaluated.inspect(),
+            "fn() {\n    let s = \"a\nb\";\n    s;\n}"
+        );
+    }
+
     #[test]
     fn test_function_application() {
         let test_cases = [
@@ -295,6 +387,29 @@ mod tests {
         helper_test_string_literal(evaluated, "foo bar");
     }
 
+    #[test]
+    fn test_string_repetition() {
+        let test_cases = [
+            (r#""x" * 3"#, "xxx"),
+            (r#"3 * "x""#, "xxx"),
+            (r#""ab" * 2 + "c""#, "ababc"),
+            (r#""ab" * 0"#, ""),
+            (r#"0 * "ab""#, ""),
+        ];
+        for tc in test_cases {
+            helper_test_string_literal(helper_test_eval(tc.0), tc.1);
+        }
+
+        helper_test_error(
+            helper_test_eval(r#""x" * -1"#),
+            "repeat count should not be negative",
+        );
+        helper_test_error(
+            helper_test_eval(r#""x" - 3"#),
+            "type mismatch: STRING - INTEGER",
+        );
+    }
+
     #[test]
     fn test_len() {
         let test_cases = [
@@ -302,6 +417,11 @@ mod tests {
             ("len([1, 2, true, 10, 20, false, \"foo\"])", 7),
             ("len([])", 0),
             ("len(\"\")", 0),
+            (r#"len({"a": 1, "b": 2})"#, 2),
+            ("len({})", 0),
+            ("let m = {}; insert(m, 1, 2); len(m)", 1),
+            ("size([1, 2, 3])", 3),
+            (r#"size({"a": 1})"#, 1),
         ];
         for tc in test_cases {
             let evaluated = helper_test_eval(tc.0);
@@ -312,7 +432,7 @@ mod tests {
         let evaluated = helper_test_eval(input);
         helper_test_error(
             evaluated,
-            "expected a STRING argument, but received an INTEGER",
+            "expected a STRING, an ARRAY or a HASH_MAP argument, but received an INTEGER",
         );
     }
 
@@ -332,6 +452,104 @@ mod tests {
         assert_eq!(captured, "12 34 foobar\n true");
     }
 
+    #[test]
+    fn test_print_output_limit() {
+        use crate::object::{
+            environment::Environment,
+            settings::{OutputLimit, Settings},
+        };
+
+        let env = Environment::new_with_settings(Settings {
+            output_limit: Some(OutputLimit::new(20)),
+            ..Default::default()
+        });
+
+        io::set_output_capture(Some(Default::default()));
+
+        let input = r#"let i = 0; while (true) { print("abcd"); i = i + 1; }"#;
+        let evaluated = helper_test_eval_with_env(input, env.clone());
+
+        let captured = std::io::set_output_capture(None);
+        let captured = captured.unwrap();
+        let captured = Arc::try_unwrap(captured).unwrap();
+        let captured = captured.into_inner().unwrap();
+        let captured = String::from_utf8(captured).unwrap();
+
+        helper_test_error(evaluated, "output limit exceeded");
+        assert_eq!(captured, "abcd".repeat(5));
+        helper_test_integer_obj(env.get("i"), 5);
+        assert_eq!(env.settings().output_limit.as_ref().unwrap().written(), 20);
+    }
+
+    #[test]
+    fn test_allocation_limit() {
+        use crate::object::{environment::Environment, settings::Settings};
+
+        let new_env = || {
+            Environment::new_with_settings(Settings {
+                allocation_limit: Some(100),
+                ..Default::default()
+            })
+        };
+
+        let error_cases = [
+            "repeat(0, 9999999999)",
+            "repeat([1, 2], 101)",
+            r#""ab" * 51"#,
+            r#"51 * "ab""#,
+            r#"repeat_str("ab", 34, ", ")"#,
+            r#"pad_left("x", 101)"#,
+            r#"center("x", 9999999999, "*")"#,
+        ];
+        for input in error_cases {
+            let evaluated = helper_test_eval_with_env(input, new_env());
+            helper_test_error(evaluated, "requested allocation exceeds limit");
+        }
+
+        // allocations up to the limit are allowed
+        let evaluated = helper_test_eval_with_env("len(repeat(0, 100))", new_env());
+        helper_test_integer_obj(evaluated, 100);
+        let evaluated = helper_test_eval_with_env(r#"len("ab" * 50)"#, new_env());
+        helper_test_integer_obj(evaluated, 100);
+        let evaluated = helper_test_eval_with_env(r#"len(repeat_str("ab", 25, ", "))"#, new_env());
+        helper_test_integer_obj(evaluated, 98);
+        let evaluated = helper_test_eval_with_env(r#"len(pad_right("x", 100))"#, new_env());
+        helper_test_integer_obj(evaluated, 100);
+    }
+
+    #[test]
+    fn test_debug() {
+        use crate::object::{environment::Environment, settings::Settings};
+        use std::{cell::RefCell, rc::Rc};
+
+        /// Collects the written bytes into a buffer that's still readable after being handed over
+        struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+        impl io::Write for SharedBuffer {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.borrow_mut().write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = Rc::new(RefCell::new(Vec::new()));
+        let env = Environment::new_with_settings(Settings {
+            debug_writer: Some(RefCell::new(Box::new(SharedBuffer(buffer.clone())))),
+            ..Default::default()
+        });
+
+        let input = r#"let x = debug(2 * 3) + 1; debug([x, "a"]); x"#;
+        helper_test_integer_obj(helper_test_eval_with_env(input, env), 7);
+
+        let written = String::from_utf8(buffer.borrow().clone()).unwrap();
+        assert_eq!(written, "[debug] 6\n[debug] [7, \"a\"]\n");
+
+        helper_test_string_literal(helper_test_eval(r#"debug("foo")"#), "foo");
+    }
+
     #[test]
     fn test_arrays() {
         let input = r#"
@@ -403,6 +621,76 @@ mod tests {
         helper_test_null(evaluated);
     }
 
+    #[test]
+    fn test_array_front_operations() {
+        use crate::object::Object;
+
+        let input = r#"
+            let q = [2];
+            push_front(q, 1);
+            push(q, 3);
+            push_front(q, 0);
+            let first = pop_front(q);
+            let last = pop(q);
+            [first, last, q]
+        "#;
+        let evaluated = helper_test_eval(input).expect(EXPECTED_ARRAY);
+        assert_eq!(evaluated.inspect(), "[0, 3, [1, 2]]");
+
+        let input = "let q = [1]; pop_front(q); pop_front(q)";
+        helper_test_null(helper_test_eval(input));
+
+        let input = "let q = []; push_front(q, 5); q";
+        helper_test_array_of_integers(helper_test_eval(input), &[5]);
+
+        helper_test_error(
+            helper_test_eval("push_front(1, 2)"),
+            "expected an ARRAY argument, but received an INTEGER",
+        );
+        helper_test_error(
+            helper_test_eval(r#"pop_front("abc")"#),
+            "expected an ARRAY argument, but received a STRING",
+        );
+    }
+
+    #[test]
+    fn test_insert_at_and_remove_at() {
+        let test_cases = [
+            ("let a = [1, 3]; insert_at(a, 1, 2); a", vec![1, 2, 3]),
+            ("let a = [2, 3]; insert_at(a, 0, 1); a", vec![1, 2, 3]),
+            ("let a = [1, 2]; insert_at(a, 2, 3); a", vec![1, 2, 3]),
+            ("let a = []; insert_at(a, 0, 1); a", vec![1]),
+            ("let a = [1, 2, 3]; remove_at(a, 1); a", vec![1, 3]),
+            ("let a = [1, 2, 3]; remove_at(a, 0); a", vec![2, 3]),
+            ("let a = [1, 2, 3]; remove_at(a, 2); a", vec![1, 2]),
+        ];
+        for tc in test_cases {
+            helper_test_array_of_integers(helper_test_eval(tc.0), &tc.1);
+        }
+
+        helper_test_integer_obj(helper_test_eval("remove_at([4, 5, 6], 1)"), 5);
+        helper_test_null(helper_test_eval("insert_at([], 0, 1)"));
+
+        let error_cases = [
+            ("insert_at([1, 2], 3, 0)", "list index out of range"),
+            ("insert_at([1, 2], -1, 0)", "list index out of range"),
+            ("remove_at([1, 2], 2)", "list index out of range"),
+            ("remove_at([], 0)", "list index out of range"),
+            ("remove_at([1], -1)", "list index out of range"),
+            (
+                r#"remove_at([1], "0")"#,
+                "expected an INTEGER argument, but received a STRING",
+            ),
+            (
+                "insert_at(1, 0, 0)",
+                "expected an ARRAY argument, but received an INTEGER",
+            ),
+        ];
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
     #[test]
     fn test_assignment_expression() {
         let test_cases = [
@@ -482,6 +770,97 @@ mod tests {
         assert_eq!(map.map.borrow().len(), 1);
     }
 
+    #[test]
+    fn test_mutable_hash_keys() {
+        let error_cases = [
+            (
+                "let m = {}; insert(m, [1, 2], 3)",
+                "an ARRAY can't be used as a hash map key, since it can be mutated",
+            ),
+            (
+                "{[1]: 2}",
+                "an ARRAY can't be used as a hash map key, since it can be mutated",
+            ),
+            (
+                "{1: 2, {}: 3}",
+                "a HASH_MAP can't be used as a hash map key, since it can be mutated",
+            ),
+            (
+                "assoc({}, [], 1)",
+                "an ARRAY can't be used as a hash map key, since it can be mutated",
+            ),
+        ];
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+
+        let input = "let m = {1: 2}; m[[1]]";
+        helper_test_null(helper_test_eval(input));
+    }
+
+    #[test]
+    fn test_operator_methods() {
+        use crate::object::Object;
+
+        let input = r#"
+            let vector = fn(x, y) {
+                let v = {"x": x, "y": y};
+                insert(v, "__add__", fn(other) { vector(x + other["x"], y + other["y"]) });
+                insert(v, "__mul__", fn(k) { vector(x * k, y * k) });
+                v
+            };
+            let sum = vector(1, 2) + vector(10, 20);
+            let scaled = sum * 2;
+            [sum["x"], sum["y"], scaled["x"], scaled["y"]]
+        "#;
+        let evaluated = helper_test_eval(input).expect(EXPECTED_ARRAY);
+        assert_eq!(evaluated.inspect(), "[11, 22, 22, 44]");
+
+        let input = r#"let m = {"__add__": fn(other) { other * 2 }}; m + 5"#;
+        helper_test_integer_obj(helper_test_eval(input), 10);
+
+        let error_cases = [
+            ("{1: 2} + {3: 4}", "unknown operator: HASH_MAP + HASH_MAP"),
+            (
+                r#"{"__add__": 1} + {}"#,
+                "unknown operator: HASH_MAP + HASH_MAP",
+            ),
+            (
+                r#"{"__sub__": fn(o) { o }} + {}"#,
+                "unknown operator: HASH_MAP + HASH_MAP",
+            ),
+            (
+                r#"1 + {"__add__": fn(o) { o }}"#,
+                "type mismatch: INTEGER + HASH_MAP",
+            ),
+            (
+                r#"{"__add__": fn() { 1 }} + 1"#,
+                "incorrect number of arguments supplied, expected: 0, supplied 1",
+            ),
+        ];
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_dot_access() {
+        let input = r#"
+            let counter = {"count": 2, "double": fn(x) { x * 2 }};
+            counter.double(counter.count) + counter["count"]
+        "#;
+        helper_test_integer_obj(helper_test_eval(input), 6);
+
+        let input = r#"let m = {"inner": {"value": 5}}; m.inner.value"#;
+        helper_test_integer_obj(helper_test_eval(input), 5);
+
+        helper_test_null(helper_test_eval(r#"{"a": 1}.b"#));
+        helper_test_error(
+            helper_test_eval("[1, 2].len"),
+            "expected an INTEGER argument, but received a STRING",
+        );
+    }
+
     #[test]
     fn test_while_statement() {
         let input = "
@@ -498,45 +877,2316 @@ mod tests {
         let evaluated = helper_test_eval(input);
         helper_test_integer_obj(evaluated, 50);
     }
-}
 
-#[cfg(test)]
-mod test_helpers {
-    use super::eval::eval;
-    use crate::{
-        lexer::Lexer,
-        object::{environment::Environment, AllObjects},
-        parser,
-    };
+    #[test]
+    fn test_array_comparisons() {
+        let test_cases = [
+            ("[1, 2] < [1, 3]", true),
+            ("[1, 2] > [1, 3]", false),
+            ("[1] < [1, 0]", true),
+            ("[1, 0] > [1]", true),
+            ("[] < [1]", true),
+            ("[1, 2] <= [1, 2]", true),
+            ("[1, 2] >= [1, 2]", true),
+            ("[1, 2] < [1, 2]", false),
+            (r#"["a", "b"] < ["a", "c"]"#, true),
+            ("[[1, 2], [3]] > [[1, 2]]", true),
+            ("[[1, 2], [3]] < [[1, 3]]", true),
+        ];
 
-    pub fn helper_test_eval(input: &str) -> Option<AllObjects> {
-        let l = Lexer::new(input);
-        let mut p = parser::Parser::new(l);
-        let program = p.parse_program();
-        let new_env = Environment::new();
+        for tc in test_cases {
+            let evaluated = helper_test_eval(tc.0);
+            helper_test_boolean_obj(evaluated, tc.1);
+        }
+
+        let evaluated = helper_test_eval(r#"[1, 2] < [1, "a"]"#);
+        helper_test_error(evaluated, "cannot compare ARRAY with ARRAY");
 
-        eval(program.make_node(), new_env)
+        let evaluated = helper_test_eval("[true] < [false]");
+        helper_test_error(evaluated, "cannot compare ARRAY with ARRAY");
+
+        let evaluated = helper_test_eval("[true] <= [true]");
+        helper_test_boolean_obj(evaluated, true);
+
+        let evaluated = helper_test_eval("[1] + [2]");
+        helper_test_error(evaluated, "unknown operator: ARRAY + ARRAY");
     }
 
-    pub fn helper_test_integer_obj(obj: Option<AllObjects>, expected: i64) {
-        let AllObjects::Integer(obj) = obj.expect(EXPECTED_OBJECT) else {
-            panic!("{}", EXPECTED_INT_OBJECT);
-        };
-        assert_eq!(obj.value, expected);
+    #[test]
+    fn test_slice() {
+        let input = "slice([1, 2, 3, 4], 1, 3)";
+        helper_test_array_of_integers(helper_test_eval(input), &[2, 3]);
+
+        let input = "slice([1, 2, 3], 1, 99)";
+        helper_test_array_of_integers(helper_test_eval(input), &[2, 3]);
+
+        let input = "slice([1, 2, 3], -5, 2)";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 2]);
+
+        let input = "slice([1, 2, 3], 2, 1)";
+        helper_test_array_of_integers(helper_test_eval(input), &[]);
+
+        let input = r#"slice("foobar", 3, 99)"#;
+        helper_test_string_literal(helper_test_eval(input), "bar");
+
+        let input = r#"slice("foobar", 4, 2)"#;
+        helper_test_string_literal(helper_test_eval(input), "");
+
+        // string indices count characters rather than bytes
+        let input = r#"slice("héllo", 0, 2)"#;
+        helper_test_string_literal(helper_test_eval(input), "hé");
+
+        let input = r#"slice("日本語", 1, 99)"#;
+        helper_test_string_literal(helper_test_eval(input), "本語");
+
+        // slice agrees with range indexing within the bounds of the string
+        for input in [r#""héllo"[1:4]"#, r#"slice("héllo", 1, 4)"#] {
+            helper_test_string_literal(helper_test_eval(input), "éll");
+        }
+
+        let input = "slice(12, 0, 1)";
+        helper_test_error(
+            helper_test_eval(input),
+            "expected an ARRAY or a STRING argument, but received an INTEGER",
+        );
     }
 
-    pub fn helper_test_string_literal(obj: Option<AllObjects>, expected: &str) {
-        let AllObjects::StringObj(obj) = obj.expect(EXPECTED_OBJECT) else{
-            panic!("{}", EXPECTED_STRING_OBJECT);
-        };
-        assert_eq!(*obj.value, expected);
+    #[test]
+    fn test_take_and_drop() {
+        let array_cases = [
+            ("take([1, 2, 3], 2)", vec![1, 2]),
+            ("take([1, 2, 3], 3)", vec![1, 2, 3]),
+            ("take([1, 2, 3], 10)", vec![1, 2, 3]),
+            ("take([1, 2, 3], 0)", vec![]),
+            ("take([1, 2, 3], -1)", vec![]),
+            ("drop([1, 2, 3], 2)", vec![3]),
+            ("drop([1, 2, 3], 3)", vec![]),
+            ("drop([1, 2, 3], 10)", vec![]),
+            ("drop([1, 2, 3], -1)", vec![1, 2, 3]),
+            ("let a = [1, 2]; take(a, 1); a", vec![1, 2]),
+        ];
+        for tc in array_cases {
+            helper_test_array_of_integers(helper_test_eval(tc.0), &tc.1);
+        }
+
+        let string_cases = [
+            (r#"take("foobar", 3)"#, "foo"),
+            (r#"take("foobar", 6)"#, "foobar"),
+            (r#"take("foobar", 99)"#, "foobar"),
+            (r#"drop("foobar", 3)"#, "bar"),
+            (r#"drop("foobar", 6)"#, ""),
+            (r#"drop("foobar", 99)"#, ""),
+            (r#"take("héllo", 2)"#, "hé"),
+            (r#"drop("héllo", 2)"#, "llo"),
+        ];
+        for tc in string_cases {
+            helper_test_string_literal(helper_test_eval(tc.0), tc.1);
+        }
+
+        helper_test_error(
+            helper_test_eval("take(12, 1)"),
+            "expected an ARRAY or a STRING argument, but received an INTEGER",
+        );
+        helper_test_error(
+            helper_test_eval(r#"drop([1], "1")"#),
+            "expected an INTEGER argument, but received a STRING",
+        );
     }
 
-    pub fn helper_test_boolean_obj(obj: Option<AllObjects>, expected: bool) {
-        let AllObjects::Boolean(obj) = obj.expect(EXPECTED_OBJECT) else {
-            panic!("{}", EXPECTED_BOOLEAN_OBJECT);
+    #[test]
+    fn test_repeat_str() {
+        let test_cases = [
+            (r#"repeat_str("ab", 3, "-")"#, "ab-ab-ab"),
+            (r#"repeat_str("ab", 1, "-")"#, "ab"),
+            (r#"repeat_str("ab", 0, "-")"#, ""),
+            (r#"repeat_str("ab", -2, "-")"#, ""),
+            (r#"repeat_str("x", 3, "")"#, "xxx"),
+            (r#"repeat_str("", 3, ", ")"#, ", , "),
+        ];
+        for tc in test_cases {
+            helper_test_string_literal(helper_test_eval(tc.0), tc.1);
+        }
+
+        let error_cases = [
+            (
+                r#"repeat_str(1, 3, "-")"#,
+                "expected a STRING argument, but received an INTEGER",
+            ),
+            (
+                r#"repeat_str("a", "3", "-")"#,
+                "expected an INTEGER argument, but received a STRING",
+            ),
+            (
+                r#"repeat_str("a", 3, null)"#,
+                "expected a STRING argument, but received a NULL",
+            ),
+            (
+                r#"repeat_str("ab", 9223372036854775807, "-")"#,
+                "integer overflow",
+            ),
+        ];
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_concat() {
+        let input = "concat([1], [2, 3], [])";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 2, 3]);
+
+        let input = "concat()";
+        helper_test_array_of_integers(helper_test_eval(input), &[]);
+
+        // the arguments are left untouched
+        let input = "let x = [1]; let y = concat(x, [2]); push(y, 3); x;";
+        helper_test_array_of_integers(helper_test_eval(input), &[1]);
+
+        let input = "concat([1], 2)";
+        helper_test_error(
+            helper_test_eval(input),
+            "expected an ARRAY argument, but received an INTEGER",
+        );
+    }
+
+    #[test]
+    fn test_copy() {
+        let input = "let a = [1, 2]; let b = copy(a); push(b, 3); a;";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 2]);
+
+        let input = "let a = [1, 2]; let b = copy(a); push(b, 3); b;";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 2, 3]);
+
+        // nested containers are copied as well
+        let input = r#"let a = {"x": [1]}; let b = copy(a); push(b["x"], 2); a["x"];"#;
+        helper_test_array_of_integers(helper_test_eval(input), &[1]);
+
+        // without copying, the array is shared
+        let input = "let a = [1, 2]; let b = a; push(b, 3); a;";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 2, 3]);
+
+        let input = "copy(5)";
+        helper_test_integer_obj(helper_test_eval(input), 5);
+    }
+
+    #[test]
+    fn test_copy_on_assign() {
+        use crate::object::{environment::Environment, settings::Settings};
+
+        let copying_env = || {
+            Environment::new_with_settings(Settings {
+                copy_on_assign: true,
+                ..Default::default()
+            })
         };
-        assert_eq!(obj.value, expected);
+
+        // by default, containers are aliased
+        let input = "let a = [1, 2]; let b = a; push(b, 3); a;";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 2, 3]);
+
+        let input = "let a = [1, 2]; let b = []; b = a; push(b, 3); a;";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 2, 3]);
+
+        // with copying enabled, each variable gets its own copy
+        let input = "let a = [1, 2]; let b = a; push(b, 3); a;";
+        let evaluated = helper_test_eval_with_env(input, copying_env());
+        helper_test_array_of_integers(evaluated, &[1, 2]);
+
+        let input = "let a = [1, 2]; let b = []; b = a; push(b, 3); a;";
+        let evaluated = helper_test_eval_with_env(input, copying_env());
+        helper_test_array_of_integers(evaluated, &[1, 2]);
+
+        let input =
+            r#"let a = {"x": 1}; let f = fn() { let b = a; insert(b, "x", 2); }; f(); a["x"]"#;
+        let evaluated = helper_test_eval_with_env(input, copying_env());
+        helper_test_integer_obj(evaluated, 1);
+    }
+
+    #[test]
+    fn test_type_predicates() {
+        let values = [
+            "5",
+            r#""foo""#,
+            "true",
+            "null",
+            "[1, 2]",
+            r#"{"a": 1}"#,
+            "fn(x) { x }",
+            "len",
+        ];
+
+        // (predicate, index of the values expected to satisfy it)
+        let test_cases = [
+            ("is_number", vec![0]),
+            ("is_string", vec![1]),
+            ("is_null", vec![3]),
+            ("is_array", vec![4]),
+            ("is_map", vec![5]),
+            ("is_function", vec![6, 7]),
+        ];
+
+        for (predicate, expected) in test_cases {
+            for (i, value) in values.iter().enumerate() {
+                let input = format!("{}({})", predicate, value);
+                let evaluated = helper_test_eval(&input);
+                helper_test_boolean_obj(evaluated, expected.contains(&i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sleep_ms() {
+        let start = Instant::now();
+        let evaluated = helper_test_eval("sleep_ms(20)");
+        helper_test_null(evaluated);
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        let evaluated = helper_test_eval("sleep_ms(-1)");
+        helper_test_error(evaluated, "sleep only accept positive integers");
+
+        let evaluated = helper_test_eval("sleep(-1)");
+        helper_test_error(evaluated, "sleep only accept positive integers");
+    }
+
+    #[test]
+    fn test_loop_statement() {
+        let input = "
+            let i = 0;
+            loop {
+                i = i + 1;
+                if (i > 4) {
+                    break;
+                }
+            }
+            i;
+        ";
+        helper_test_integer_obj(helper_test_eval(input), 5);
+
+        let input = "
+            let i = 0;
+            let find = fn() {
+                loop {
+                    i = i + 1;
+                    if (i * i > 50) {
+                        break i * 10;
+                    }
+                }
+            };
+            find();
+        ";
+        helper_test_integer_obj(helper_test_eval(input), 80);
+
+        // a return inside of a loop returns from the enclosing function
+        let input = "let f = fn() { loop { return 7; } }; f();";
+        helper_test_integer_obj(helper_test_eval(input), 7);
+
+        // each iteration gets its own scope
+        let input = "let i = 0; loop { let x = i; i = i + 1; if (i > 2) { break x; } }";
+        helper_test_integer_obj(helper_test_eval(input), 2);
+
+        let input = "let i = 0; while (true) { i = i + 1; if (i == 3) { break; } } i;";
+        helper_test_integer_obj(helper_test_eval(input), 3);
+    }
+
+    #[test]
+    fn test_break_outside_loop() {
+        let evaluated = helper_test_eval("break;");
+        helper_test_error(evaluated, "break outside of a loop");
+
+        let evaluated = helper_test_eval("let f = fn() { break 5; }; loop { f(); }");
+        helper_test_error(evaluated, "break outside of a loop");
+    }
+
+    #[test]
+    fn test_each_builtin() {
+        let input = "
+            let out = [];
+            each([1, 2, 3], fn(x) { push(out, x * 2); });
+            out;
+        ";
+        helper_test_array_of_integers(helper_test_eval(input), &[2, 4, 6]);
+
+        let input = "
+            let out = [];
+            each({\"b\": 2, \"a\": 1, \"c\": 3}, fn(k, v) { push(out, v); });
+            out;
+        ";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 2, 3]);
+
+        // pushing to the array being iterated doesn't extend the iteration
+        let input = "let arr = [1, 2]; each(arr, fn(x) { push(arr, x); }); arr;";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 2, 1, 2]);
+
+        helper_test_null(helper_test_eval("each([1], fn(x) { x })"));
+
+        let test_cases = [
+            (
+                "each([1, 2], fn(k, v) { k })",
+                "incorrect number of callback parameters, expected: 1, found 2",
+            ),
+            (
+                "each({1: 2}, fn(x) { x })",
+                "incorrect number of callback parameters, expected: 2, found 1",
+            ),
+            (
+                "each([1], 5)",
+                "expected a FUNCTION argument, but received an INTEGER",
+            ),
+            (
+                "each(5, fn(x) { x })",
+                "expected an ARRAY or a hash map argument, but received an INTEGER",
+            ),
+            (
+                "each([1, 2], fn(x) { x + true })",
+                "type mismatch: INTEGER + BOOLEAN",
+            ),
+        ];
+
+        for tc in test_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_sum_and_product_builtins() {
+        let test_cases = [
+            ("sum([1, 2, 3, 4])", 10),
+            ("sum([])", 0),
+            ("sum([-5, 5, 7])", 7),
+            ("product([1, 2, 3, 4])", 24),
+            ("product([])", 1),
+            ("product([-2, 3])", -6),
+        ];
+
+        for tc in test_cases {
+            helper_test_integer_obj(helper_test_eval(tc.0), tc.1);
+        }
+
+        let test_cases = [
+            ("sum([9223372036854775807, 1])", "integer overflow"),
+            ("product([9223372036854775807, 2])", "integer overflow"),
+            (
+                "sum([1, \"2\"])",
+                "expected INTEGER elements, but found a STRING",
+            ),
+            (
+                "product(5)",
+                "expected an ARRAY argument, but received an INTEGER",
+            ),
+        ];
+
+        for tc in test_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_results() {
+        use crate::object::Object;
+
+        let input = r#"
+            let divide = fn(a, b) {
+                if (b == 0) { return err("division by zero"); }
+                ok(a / b)
+            };
+            let good = divide(10, 2);
+            let bad = divide(1, 0);
+            [is_ok(good), is_err(good), is_ok(bad), is_err(bad), unwrap(good), bad["__err__"]]
+        "#;
+        let evaluated = helper_test_eval(input).expect(EXPECTED_ARRAY);
+        assert_eq!(
+            evaluated.inspect(),
+            r#"[true, false, false, true, 5, "division by zero"]"#
+        );
+
+        helper_test_null(helper_test_eval("unwrap(ok(null))"));
+
+        let test_cases = ["is_ok(1)", "is_err(1)", "is_ok({})", r#"is_err({"a": 1})"#];
+        for input in test_cases {
+            helper_test_boolean_obj(helper_test_eval(input), false);
+        }
+
+        let error_cases = [
+            (
+                r#"unwrap(err("not found"))"#,
+                "called unwrap on an err: not found",
+            ),
+            ("unwrap(err([1]))", "called unwrap on an err: [1]"),
+            (
+                "unwrap(5)",
+                "expected a result argument, but received an INTEGER",
+            ),
+            (r#"ok(1 + "a")"#, "type mismatch: INTEGER + STRING"),
+        ];
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_step_by() {
+        let test_cases = [
+            ("step_by([0, 1, 2, 3, 4, 5], 2)", vec![0, 2, 4]),
+            ("step_by([0, 1, 2, 3, 4, 5], 3)", vec![0, 3]),
+            ("step_by([0, 1, 2], 1)", vec![0, 1, 2]),
+            ("step_by([0, 1, 2], 10)", vec![0]),
+            ("step_by([], 2)", vec![]),
+        ];
+        for tc in test_cases {
+            helper_test_array_of_integers(helper_test_eval(tc.0), &tc.1);
+        }
+
+        for input in ["step_by([1, 2], 0)", "step_by([1, 2], -2)"] {
+            helper_test_error(
+                helper_test_eval(input),
+                "step size should be a positive integer",
+            );
+        }
+
+        helper_test_error(
+            helper_test_eval(r#"step_by("abc", 2)"#),
+            "expected an ARRAY argument, but received a STRING",
+        );
+    }
+
+    #[test]
+    fn test_chunk() {
+        let evaluated = helper_test_eval("chunk([1, 2, 3, 4, 5], 2)");
+        let AllObjects::ArrayObj(chunks) = evaluated.expect(EXPECTED_ARRAY) else {
+            panic!("{}", EXPECTED_ARRAY);
+        };
+        let chunks = chunks.elements.borrow();
+        assert_eq!(chunks.len(), 3);
+        helper_test_array_of_integers(Some(chunks[0].clone()), &[1, 2]);
+        helper_test_array_of_integers(Some(chunks[1].clone()), &[3, 4]);
+        helper_test_array_of_integers(Some(chunks[2].clone()), &[5]);
+
+        let input = "len(chunk([1, 2, 3], 5))";
+        helper_test_integer_obj(helper_test_eval(input), 1);
+
+        let input = "chunk([], 2)";
+        helper_test_array_of_integers(helper_test_eval(input), &[]);
+
+        let test_cases = ["chunk([1, 2], 0)", "chunk([1, 2], -1)"];
+        for tc in test_cases {
+            helper_test_error(
+                helper_test_eval(tc),
+                "chunk size should be a positive integer",
+            );
+        }
+
+        let input = "chunk(5, 2)";
+        helper_test_error(
+            helper_test_eval(input),
+            "expected an ARRAY argument, but received an INTEGER",
+        );
+    }
+
+    #[test]
+    fn test_zip() {
+        let test_cases = [
+            ("zip([1, 2, 3], [4, 5, 6])", vec![[1, 4], [2, 5], [3, 6]]),
+            ("zip([1, 2, 3], [4])", vec![[1, 4]]),
+            ("zip([1], [4, 5, 6])", vec![[1, 4]]),
+            ("let a = [1, 2]; zip(a, a)", vec![[1, 1], [2, 2]]),
+            ("zip([], [1])", vec![]),
+        ];
+
+        for tc in test_cases {
+            let evaluated = helper_test_eval(tc.0);
+            let AllObjects::ArrayObj(pairs) = evaluated.expect(EXPECTED_ARRAY) else {
+                panic!("{}", EXPECTED_ARRAY);
+            };
+            let pairs = pairs.elements.borrow();
+            assert_eq!(pairs.len(), tc.1.len());
+            for (pair, expected) in pairs.iter().zip(tc.1) {
+                helper_test_array_of_integers(Some(pair.clone()), &expected);
+            }
+        }
+
+        let input = "zip([1], 2)";
+        helper_test_error(
+            helper_test_eval(input),
+            "expected an ARRAY argument, but received an INTEGER",
+        );
+    }
+
+    #[test]
+    fn test_repeat() {
+        let evaluated = helper_test_eval(r#"repeat("x", 3)"#);
+        let AllObjects::ArrayObj(arr) = evaluated.expect(EXPECTED_ARRAY) else {
+            panic!("{}", EXPECTED_ARRAY);
+        };
+        assert_eq!(arr.elements.borrow().len(), 3);
+        for element in arr.elements.borrow().iter() {
+            helper_test_string_literal(Some(element.clone()), "x");
+        }
+
+        // the repeated arrays are independent of each other
+        let input = "let a = repeat([1], 2); push(a[0], 2); a[1];";
+        helper_test_array_of_integers(helper_test_eval(input), &[1]);
+
+        let input = "let a = repeat([1], 2); push(a[0], 2); a[0];";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 2]);
+
+        let input = "repeat(7, 0)";
+        helper_test_array_of_integers(helper_test_eval(input), &[]);
+
+        let input = "repeat(7, -1)";
+        helper_test_error(
+            helper_test_eval(input),
+            "repeat count should not be negative",
+        );
+    }
+
+    #[test]
+    fn test_min_by_and_max_by() {
+        let input = r#"let arr = ["ab", "abcd", "a", "efgh"]; max_by(arr, fn(s) { len(s) })"#;
+        helper_test_string_literal(helper_test_eval(input), "abcd");
+
+        let input = r#"let arr = ["ab", "abcd", "a", "efgh"]; min_by(arr, fn(s) { len(s) })"#;
+        helper_test_string_literal(helper_test_eval(input), "a");
+
+        let input = "min_by([3, -7, 5], fn(x) { x * x })";
+        helper_test_integer_obj(helper_test_eval(input), 3);
+
+        let input = r#"max_by([{"n": "b"}, {"n": "c"}, {"n": "a"}], fn(m) { m["n"] })["n"]"#;
+        helper_test_string_literal(helper_test_eval(input), "c");
+
+        helper_test_null(helper_test_eval("max_by([], fn(x) { x })"));
+
+        let input = r#"max_by([1, 2], fn(x) { if (x == 1) { 1 } else { "a" } })"#;
+        helper_test_error(
+            helper_test_eval(input),
+            "cannot compare STRING with INTEGER",
+        );
+    }
+
+    #[test]
+    fn test_find_and_find_index() {
+        let input = "find([1, 3, 4, 5, 6], fn(x) { x / 2 * 2 == x })";
+        helper_test_integer_obj(helper_test_eval(input), 4);
+
+        let input = "find_index([1, 3, 4, 5, 6], fn(x) { x / 2 * 2 == x })";
+        helper_test_integer_obj(helper_test_eval(input), 2);
+
+        let input = "find([1, 3, 5], fn(x) { x / 2 * 2 == x })";
+        helper_test_null(helper_test_eval(input));
+
+        let input = "find_index([1, 3, 5], fn(x) { x / 2 * 2 == x })";
+        helper_test_integer_obj(helper_test_eval(input), -1);
+
+        let input = "find_index([], fn(x) { true })";
+        helper_test_integer_obj(helper_test_eval(input), -1);
+
+        let input = "find([1], fn(x, y) { true })";
+        helper_test_error(
+            helper_test_eval(input),
+            "incorrect number of callback parameters, expected: 1, found 2",
+        );
+    }
+
+    #[test]
+    fn test_all_and_any() {
+        let test_cases = [
+            ("all([2, 4, 6], fn(x) { x > 1 })", true),
+            ("all([2, 0, 6], fn(x) { x > 1 })", false),
+            ("all([], fn(x) { false })", true),
+            ("any([0, 1, 2], fn(x) { x > 1 })", true),
+            ("any([0, 1], fn(x) { x > 1 })", false),
+            ("any([], fn(x) { true })", false),
+        ];
+
+        for tc in test_cases {
+            helper_test_boolean_obj(helper_test_eval(tc.0), tc.1);
+        }
+
+        // the predicate is not called after the result is known
+        let input = "let count = 0; all([1, 0, 2, 3], fn(x) { count = count + 1; x > 0 }); count;";
+        helper_test_integer_obj(helper_test_eval(input), 2);
+
+        let input = "let count = 0; any([0, 1, 2, 3], fn(x) { count = count + 1; x > 0 }); count;";
+        helper_test_integer_obj(helper_test_eval(input), 2);
+    }
+
+    #[test]
+    fn test_flat() {
+        use crate::object::Object;
+
+        let input = "flat([[1, 2], [3], 4])";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 2, 3, 4]);
+
+        let input = "flat([[1, [2, [3]]], [[4]]], 2)";
+        let evaluated = helper_test_eval(input).expect(EXPECTED_ARRAY);
+        assert_eq!(evaluated.inspect(), "[1, 2, [3], 4]");
+
+        let input = "flat([[1, [2, [3]]], [[4]]], 10)";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 2, 3, 4]);
+
+        let input = "len(flat([[1, 2], [3]], 0))";
+        helper_test_integer_obj(helper_test_eval(input), 2);
+
+        let test_cases = [
+            ("flat([1], -1)", "flat depth should not be negative"),
+            (
+                "flat()",
+                "incorrect number of arguments supplied, expected: 1 to 2, supplied 0",
+            ),
+            (
+                "flat([1], 1, 2)",
+                "incorrect number of arguments supplied, expected: 1 to 2, supplied 3",
+            ),
+            (
+                "flat(1)",
+                "expected an ARRAY argument, but received an INTEGER",
+            ),
+        ];
+
+        for tc in test_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_count() {
+        let test_cases = [
+            ("count([1, 2, 1, 3, 1], 1)", 3),
+            (r#"count(["a", "b", "a"], "a")"#, 2),
+            ("count([[1], [2], [1]], [1])", 2),
+            ("count([1, 2, 3], 4)", 0),
+            ("count([1, 2, 3, 4, 5], fn(x) { x > 2 })", 3),
+            ("count([], fn(x) { true })", 0),
+        ];
+
+        for tc in test_cases {
+            helper_test_integer_obj(helper_test_eval(tc.0), tc.1);
+        }
+
+        let input = "count(5, 1)";
+        helper_test_error(
+            helper_test_eval(input),
+            "expected an ARRAY argument, but received an INTEGER",
+        );
+    }
+
+    #[test]
+    fn test_min_and_max() {
+        let test_cases = [
+            ("max(3)", 3),
+            ("max(3, 9, -2)", 9),
+            ("min(3, 9, -2)", -2),
+            ("min(4, 4, 5)", 4),
+        ];
+
+        for tc in test_cases {
+            helper_test_integer_obj(helper_test_eval(tc.0), tc.1);
+        }
+
+        let input = r#"max("apple", "pear", "fig")"#;
+        helper_test_string_literal(helper_test_eval(input), "pear");
+
+        let input = "max([1, 2], [1, 3])";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 3]);
+
+        let test_cases = [
+            (
+                "max()",
+                "incorrect number of arguments supplied, expected at least: 1, supplied 0",
+            ),
+            (
+                "min()",
+                "incorrect number of arguments supplied, expected at least: 1, supplied 0",
+            ),
+            ("max(1, \"a\")", "cannot compare STRING with INTEGER"),
+        ];
+
+        for tc in test_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_panic() {
+        let test_cases = [
+            r#"panic("boom"); 5;"#,
+            r#"let f = fn() { panic("boom"); 1 }; f() + 2;"#,
+            r#"let i = 0; loop { i = i + 1; if (i == 3) { panic("boom") } } i;"#,
+            r#"each([1, 2], fn(x) { panic("boom") }); 5"#,
+        ];
+
+        for tc in test_cases {
+            match helper_test_eval(tc).expect(EXPECTED_ERROR) {
+                AllObjects::FatalError(e) => assert_eq!(e.message, "boom"),
+                _ => panic!("expected a fatal error for {}", tc),
+            }
+        }
+
+        let evaluated = helper_test_eval("panic([1, 2])").expect(EXPECTED_ERROR);
+        assert!(matches!(evaluated, AllObjects::FatalError(e) if e.message == "[1, 2]"));
+    }
+
+    #[test]
+    fn test_exit() {
+        let test_cases = [
+            ("exit(2); 5;", 2),
+            ("let f = fn() { exit(0); 1 }; f() + 2;", 0),
+            ("loop { each([1], fn(x) { exit(3) }) }", 3),
+            ("exit(255)", 255),
+        ];
+
+        for tc in test_cases {
+            match helper_test_eval(tc.0) {
+                Some(AllObjects::ExitSignal(code)) => assert_eq!(code, tc.1),
+                _ => panic!("expected an exit signal for {}", tc.0),
+            }
+        }
+
+        helper_test_error(
+            helper_test_eval(r#"exit("1")"#),
+            "expected an INTEGER argument, but received a STRING",
+        );
+
+        // codes the operating system can't report are rejected instead of being truncated
+        let error_cases = [
+            (
+                "exit(256)",
+                "exit code should be between 0 and 255, but received 256",
+            ),
+            (
+                "exit(-1)",
+                "exit code should be between 0 and 255, but received -1",
+            ),
+            (
+                "exit(4294967296)",
+                "exit code should be between 0 and 255, but received 4294967296",
+            ),
+        ];
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_parse_int() {
+        let test_cases = [
+            (r#"parse_int("ff", 16)"#, 255),
+            (r#"parse_int("FF", 16)"#, 255),
+            (r#"parse_int("101", 2)"#, 5),
+            (r#"parse_int("-42", 10)"#, -42),
+            (r#"parse_int("z", 36)"#, 35),
+        ];
+
+        for tc in test_cases {
+            helper_test_integer_obj(helper_test_eval(tc.0), tc.1);
+        }
+
+        let error_cases = [
+            (
+                r#"parse_int("10", 1)"#,
+                "radix should be between 2 and 36, but received 1",
+            ),
+            (
+                r#"parse_int("10", 37)"#,
+                "radix should be between 2 and 36, but received 37",
+            ),
+            (
+                r#"parse_int("12", 2)"#,
+                r#"could not parse "12" as an integer in radix 2"#,
+            ),
+            (
+                r#"parse_int("", 10)"#,
+                r#"could not parse "" as an integer in radix 10"#,
+            ),
+            (
+                "parse_int(10, 10)",
+                "expected a STRING argument, but received an INTEGER",
+            ),
+        ];
+
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_to_radix() {
+        let test_cases = [
+            ("to_radix(255, 16)", "ff"),
+            ("to_radix(5, 2)", "101"),
+            ("to_radix(0, 2)", "0"),
+            ("to_radix(-42, 10)", "-42"),
+            ("to_radix(35, 36)", "z"),
+            (
+                "to_radix(-9223372036854775807 - 1, 16)",
+                "-8000000000000000",
+            ),
+            (r#"to_radix(parse_int("-zz", 36), 36)"#, "-zz"),
+        ];
+
+        for tc in test_cases {
+            helper_test_string_literal(helper_test_eval(tc.0), tc.1);
+        }
+
+        let error_cases = [
+            (
+                "to_radix(10, 1)",
+                "radix should be between 2 and 36, but received 1",
+            ),
+            (
+                "to_radix(10, 37)",
+                "radix should be between 2 and 36, but received 37",
+            ),
+            (
+                r#"to_radix("10", 10)"#,
+                "expected an INTEGER argument, but received a STRING",
+            ),
+        ];
+
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_ord_and_chr() {
+        let test_cases = [
+            (r#"ord("A")"#, 65),
+            (r#"ord("é")"#, 233),
+            (r#"ord(chr(128512))"#, 128512),
+        ];
+        for tc in test_cases {
+            helper_test_integer_obj(helper_test_eval(tc.0), tc.1);
+        }
+
+        helper_test_string_literal(helper_test_eval("chr(97)"), "a");
+        helper_test_string_literal(helper_test_eval(r#"chr(ord("a") + 2)"#), "c");
+
+        let error_cases = [
+            ("chr(-1)", "-1 is not a valid code point"),
+            ("chr(55296)", "55296 is not a valid code point"),
+            ("chr(1114112)", "1114112 is not a valid code point"),
+            (
+                r#"ord("ab")"#,
+                r#"expected a single character, but received "ab""#,
+            ),
+            (
+                r#"ord("")"#,
+                r#"expected a single character, but received """#,
+            ),
+            (
+                "ord(65)",
+                "expected a STRING argument, but received an INTEGER",
+            ),
+        ];
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_inspect_sorted() {
+        let input = r#"
+            let a = {};
+            let b = {};
+            let keys = ["delta", "alpha", "charlie", "bravo", "echo"];
+            each(keys, fn(k) { insert(a, k, len(k)); });
+            each([4, 3, 2, 1, 0], fn(i) { insert(b, keys[i], len(keys[i])); });
+            insert(a, "nested", {"z": [{"y": 1, "x": 2}], "w": 0});
+            insert(b, "nested", {"w": 0, "z": [{"x": 2, "y": 1}]});
+            [inspect_sorted(a), inspect_sorted(a) == inspect_sorted(b)]
+        "#;
+
+        let AllObjects::ArrayObj(arr) = helper_test_eval(input).expect(EXPECTED_ARRAY) else {
+            panic!("{}", EXPECTED_ARRAY);
+        };
+        let elements = arr.elements.borrow();
+        helper_test_string_literal(
+            Some(elements[0].clone()),
+            r#"{ "alpha":5, "bravo":5, "charlie":7, "delta":5, "echo":4, "nested":{ "w":0, "z":[{ "x":2, "y":1 }] } }"#,
+        );
+        helper_test_boolean_obj(Some(elements[1].clone()), true);
+
+        helper_test_string_literal(helper_test_eval("inspect_sorted([3, 1, 2])"), "[3, 1, 2]");
+        helper_test_string_literal(helper_test_eval(r#"inspect_sorted("a")"#), "a");
+    }
+
+    #[test]
+    fn test_getenv() {
+        use crate::object::{environment::Environment, settings::Settings};
+
+        std::env::set_var("MONKEY_TEST_GETENV", "hello");
+        std::env::remove_var("MONKEY_TEST_GETENV_UNSET");
+
+        let input = r#"getenv("MONKEY_TEST_GETENV")"#;
+        helper_test_string_literal(helper_test_eval(input), "hello");
+
+        let input = r#"getenv("MONKEY_TEST_GETENV_UNSET")"#;
+        helper_test_null(helper_test_eval(input));
+
+        helper_test_error(
+            helper_test_eval("getenv(1)"),
+            "expected a STRING argument, but received an INTEGER",
+        );
+
+        let sandboxed_env = Environment::new_with_settings(Settings {
+            sandboxed: true,
+            ..Default::default()
+        });
+        let input = r#"let f = fn() { getenv("MONKEY_TEST_GETENV") }; f()"#;
+        helper_test_error(
+            helper_test_eval_with_env(input, sandboxed_env),
+            "getenv is not available in sandboxed mode",
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let test_cases = [
+            (r#"lines("a\nb\nc")"#, vec!["a", "b", "c"]),
+            (r#"lines("a\r\nb\r\nc")"#, vec!["a", "b", "c"]),
+            (r#"lines("a\nb\n")"#, vec!["a", "b"]),
+            (r#"lines("a\r\n\r\nb\r\n")"#, vec!["a", "", "b"]),
+            (
+                "lines(\"first\r\nsecond\nthird\n\")",
+                vec!["first", "second", "third"],
+            ),
+            (r#"lines("single")"#, vec!["single"]),
+            (r#"lines("")"#, vec![]),
+        ];
+
+        for tc in test_cases {
+            let AllObjects::ArrayObj(arr) = helper_test_eval(tc.0).expect(EXPECTED_ARRAY) else {
+                panic!("{}", EXPECTED_ARRAY);
+            };
+            let elements = arr.elements.borrow();
+            assert_eq!(elements.len(), tc.1.len(), "input: {}", tc.0);
+            for (element, expected) in elements.iter().zip(tc.1) {
+                helper_test_string_literal(Some(element.clone()), expected);
+            }
+        }
+
+        helper_test_error(
+            helper_test_eval("lines(1)"),
+            "expected a STRING argument, but received an INTEGER",
+        );
+    }
+
+    #[test]
+    fn test_string_builder() {
+        let input = r#"
+            let b = string_builder();
+            let i = 0;
+            while (i < 10000) {
+                append(b, "ab");
+                i = i + 1;
+            }
+            build(b)
+        "#;
+        helper_test_string_literal(helper_test_eval(input), &"ab".repeat(10000));
+
+        let input = r#"
+            let b = string_builder();
+            append(b, "n=");
+            append(b, 1);
+            append(b, [true, "x"]);
+            let first = build(b);
+            append(b, "!");
+            first + " " + build(b)
+        "#;
+        helper_test_string_literal(helper_test_eval(input), r#"n=1[true, "x"] n=1[true, "x"]!"#);
+
+        let test_cases = [
+            ("build(string_builder())", ""),
+            ("inspect_sorted(string_builder())", r#"string_builder("")"#),
+        ];
+        for tc in test_cases {
+            helper_test_string_literal(helper_test_eval(tc.0), tc.1);
+        }
+
+        // builders share their contents like arrays, unless they're copied
+        let input = r#"let a = string_builder(); let b = a; append(b, "x"); build(a)"#;
+        helper_test_string_literal(helper_test_eval(input), "x");
+        let input = r#"let a = string_builder(); let b = copy(a); append(b, "x"); build(a)"#;
+        helper_test_string_literal(helper_test_eval(input), "");
+
+        let error_cases = [
+            (
+                r#"append("a", "b")"#,
+                "expected a STRING_BUILDER argument, but received a STRING",
+            ),
+            (
+                "build([])",
+                "expected a STRING_BUILDER argument, but received an ARRAY",
+            ),
+            (
+                "append(string_builder(), missing)",
+                "identifier not found: missing",
+            ),
+            (
+                "{string_builder(): 1}",
+                "a STRING_BUILDER can't be used as a hash map key, since it can be mutated",
+            ),
+        ];
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_trim() {
+        let test_cases = [
+            (r#"trim("  a b  ")"#, "a b"),
+            (r#"trim_start("  a b  ")"#, "a b  "),
+            (r#"trim_end("  a b  ")"#, "  a b"),
+            ("trim(\"\n\t x \n\")", "x"),
+            (r#"trim("   ")"#, ""),
+            (r#"trim_chars("--a-b!?", "-!?")"#, "a-b"),
+            (r#"trim_chars("...done.", ".")"#, "done"),
+            (r#"trim_chars("  x  ", "")"#, "  x  "),
+            (r#"trim_chars("abcba", "ab")"#, "c"),
+        ];
+
+        for tc in test_cases {
+            helper_test_string_literal(helper_test_eval(tc.0), tc.1);
+        }
+
+        let error_cases = [
+            (
+                "trim(1)",
+                "expected a STRING argument, but received an INTEGER",
+            ),
+            (
+                "trim_start([])",
+                "expected a STRING argument, but received an ARRAY",
+            ),
+            (
+                "trim_end(true)",
+                "expected a STRING argument, but received a BOOLEAN",
+            ),
+            (
+                r#"trim_chars(1, "-")"#,
+                "expected a STRING argument, but received an INTEGER",
+            ),
+            (
+                r#"trim_chars("-a-", 1)"#,
+                "expected a STRING argument, but received an INTEGER",
+            ),
+        ];
+
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_pad() {
+        let test_cases = [
+            ("pad_left(42, 5)", "   42"),
+            ("pad_left(12345, 3)", "12345"),
+            (r#"pad_right("ab", 4)"#, "ab  "),
+            (r#"pad_left(7, 3, "0")"#, "007"),
+            (r#"pad_right([1], 6, "-")"#, "[1]---"),
+            (r#"pad_left("x", -1)"#, "x"),
+            (r#"center("ab", 6)"#, "  ab  "),
+            (r#"center("abc", 6)"#, " abc  "),
+            (r#"center("ab", 7, "*")"#, "**ab***"),
+            (r#"center("abcdef", 4)"#, "abcdef"),
+            ("center(5, 3)", " 5 "),
+        ];
+
+        for tc in test_cases {
+            helper_test_string_literal(helper_test_eval(tc.0), tc.1);
+        }
+
+        let input = r#"
+            let rows = [];
+            each([1, 22, 333], fn(n) { push(rows, pad_left(n, 4)) });
+            rows
+        "#;
+        let AllObjects::ArrayObj(arr) = helper_test_eval(input).expect(EXPECTED_ARRAY) else {
+            panic!("{}", EXPECTED_ARRAY);
+        };
+        for (row, expected) in arr.elements.borrow().iter().zip(["   1", "  22", " 333"]) {
+            helper_test_string_literal(Some(row.clone()), expected);
+        }
+
+        let error_cases = [
+            (
+                r#"pad_left(1, 3, "ab")"#,
+                "pad fill should be a single character",
+            ),
+            (
+                r#"pad_left(1, 3, "")"#,
+                "pad fill should be a single character",
+            ),
+            (
+                "pad_right(1, 3, 0)",
+                "expected a STRING argument, but received an INTEGER",
+            ),
+            (
+                r#"pad_right(1, "3")"#,
+                "expected an INTEGER argument, but received a STRING",
+            ),
+            (
+                r#"pad_left(1, 2, "0", 4)"#,
+                "incorrect number of arguments supplied, expected: 2 to 3, supplied 4",
+            ),
+            (
+                "pad_left(1)",
+                "incorrect number of arguments supplied, expected: 2 to 3, supplied 1",
+            ),
+            (
+                "pad_left(1, 3, null)",
+                "expected a STRING argument, but received a NULL",
+            ),
+            (
+                r#"center("a", 3, "--")"#,
+                "pad fill should be a single character",
+            ),
+        ];
+
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_entries() {
+        use crate::object::Object;
+
+        let input = r#"let m = {"b": 2, "a": 1}; insert(m, "c", [3]); entries(m)"#;
+        let evaluated = helper_test_eval(input).expect(EXPECTED_ARRAY);
+        assert_eq!(evaluated.inspect(), r#"[["a", 1], ["b", 2], ["c", [3]]]"#);
+
+        let input = r#"
+            let m = {1: 10, 2: 20, 3: 30};
+            let total = 0;
+            each(entries(m), fn(pair) { total = total + pair[0] * pair[1] });
+            total
+        "#;
+        helper_test_integer_obj(helper_test_eval(input), 140);
+
+        let input = "entries({})";
+        helper_test_array_of_integers(helper_test_eval(input), &[]);
+
+        helper_test_error(
+            helper_test_eval("entries([1, 2])"),
+            "expected a hash map argument, but received an ARRAY",
+        );
+    }
+
+    #[test]
+    fn test_sorted_keys() {
+        use crate::object::Object;
+
+        let input = r#"
+            let a = {"b": 1, 10: 2, "a": 3, 2: 4, "1": 5, 1: 6};
+            let b = {};
+            insert(b, 1, 0);
+            insert(b, "1", 0);
+            insert(b, 2, 0);
+            insert(b, "a", 0);
+            insert(b, 10, 0);
+            insert(b, "b", 0);
+            [sorted_keys(a), sorted_keys(b)]
+        "#;
+        let evaluated = helper_test_eval(input).expect(EXPECTED_ARRAY);
+        let expected = r#"[1, "1", 10, 2, "a", "b"]"#;
+        assert_eq!(evaluated.inspect(), format!("[{}, {}]", expected, expected));
+
+        let input = "sorted_keys({})";
+        helper_test_array_of_integers(helper_test_eval(input), &[]);
+
+        helper_test_error(
+            helper_test_eval("sorted_keys([1, 2])"),
+            "expected a hash map argument, but received an ARRAY",
+        );
+    }
+
+    #[test]
+    fn test_registered_builtins() {
+        use crate::object::environment::Environment;
+        use crate::object::objects::{ErrorKind, Integer, ParamsType};
+        use std::rc::Rc;
+
+        fn double(env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+            match env.get("x") {
+                Some(AllObjects::Integer(v)) => AllObjects::Integer(Integer { value: v.value * 2 }),
+                _ => AllObjects::new_error(ErrorKind::InvalidArgument, "double expects an integer"),
+            }
+        }
+
+        fn len(_env: Rc<Environment>, _caller: Rc<Environment>) -> AllObjects {
+            AllObjects::Integer(Integer { value: -1 })
+        }
+
+        let new_env = || {
+            let env = Environment::new();
+            env.register("double", ParamsType::Fixed(vec!["x".to_string()]), double);
+            env.register("len", ParamsType::Variadic, len);
+            env
+        };
+
+        let input = "let f = fn(n) { double(n) + 1 }; f(double(5))";
+        helper_test_integer_obj(helper_test_eval_with_env(input, new_env()), 21);
+
+        let input = "len([1, 2, 3])";
+        helper_test_integer_obj(helper_test_eval_with_env(input, new_env()), -1);
+
+        helper_test_error(
+            helper_test_eval_with_env(r#"double("a")"#, new_env()),
+            "double expects an integer",
+        );
+        helper_test_error(
+            helper_test_eval("double(2)"),
+            "identifier not found: double",
+        );
+    }
+
+    #[test]
+    fn test_compose_and_pipe() {
+        use crate::object::Object;
+
+        let input = r#"
+            let add_one = fn(x) { x + 1 };
+            let double = fn(x) { x * 2 };
+            let f = compose(add_one, double);
+            let g = compose(double, add_one);
+            [f(5), g(5), compose(len, fn(s) { s + "!" })("abc")]
+        "#;
+        helper_test_array_of_integers(helper_test_eval(input), &[11, 12, 4]);
+
+        // composed functions can be composed further
+        let input = r#"
+            let double = fn(x) { x * 2 };
+            let f = compose(double, compose(double, double));
+            [f(5), compose(f, f)(1)]
+        "#;
+        helper_test_array_of_integers(helper_test_eval(input), &[40, 64]);
+
+        // composed and partially applied functions can be combined
+        let input = r#"
+            let add = fn(x, y) { x + y };
+            let double = fn(x) { x * 2 };
+            let f = compose(partial(add, 1), compose(double, double));
+            [f(5), compose(f, f)(0), partial(f, 2)()]
+        "#;
+        helper_test_array_of_integers(helper_test_eval(input), &[21, 5, 9]);
+
+        // the composed function is shown as the call which created it
+        let input = "compose(len, fn(x) { x })";
+        let evaluated = helper_test_eval(input).expect(EXPECTED_OBJECT);
+        assert_eq!(evaluated.inspect(), "compose(len, fn(x) {\n    x;\n})");
+
+        let input = r#"
+            let add_one = fn(x) { x + 1 };
+            let double = fn(x) { x * 2 };
+            let square = fn(x) { x * x };
+            [pipe(3, add_one, double, square), pipe(3, square, double, add_one), pipe(3)]
+        "#;
+        helper_test_array_of_integers(helper_test_eval(input), &[64, 19, 3]);
+
+        let error_cases = [
+            (
+                "compose(fn(x) { x }, fn(x, y) { x })",
+                "incorrect number of callback parameters, expected: 1, found 2",
+            ),
+            (
+                "pipe(1, fn(x) { x }, 5)",
+                "expected a FUNCTION argument, but received an INTEGER",
+            ),
+            (
+                "pipe(1, fn(x) { x + true }, fn(x) { x })",
+                "type mismatch: INTEGER + BOOLEAN",
+            ),
+            (
+                "pipe()",
+                "incorrect number of arguments supplied, expected at least: 1, supplied 0",
+            ),
+            (
+                "compose(len, len)()",
+                "incorrect number of arguments supplied, expected: 1, supplied 0",
+            ),
+        ];
+
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_partial() {
+        use crate::object::Object;
+
+        let input = r#"
+            let add = fn(x, y) { x + y };
+            let add_five = partial(add, 5);
+            let sub3 = fn(a, b, c) { a - b - c };
+            let arr = [1, 2];
+            partial(push, arr)(3);
+            [
+                add_five(3),
+                add_five(10),
+                partial(sub3, 10)(2, 3),
+                partial(sub3, 10, 2)(3),
+                partial(sub3, 10, 2, 3)(),
+                partial(add)(1, 2),
+                len(arr),
+            ]
+        "#;
+        helper_test_array_of_integers(helper_test_eval(input), &[8, 15, 5, 5, 5, 3, 3]);
+
+        // the bound values are shown instead of the names they're stored under
+        let input = r#"partial(push, [1], "b")"#;
+        let evaluated = helper_test_eval(input).expect(EXPECTED_OBJECT);
+        assert_eq!(evaluated.inspect(), r#"partial(push, [1], "b")"#);
+
+        let input = "let add = fn(x, y) { x + y }; each([1, 2], partial(add, 1));";
+        helper_test_null(helper_test_eval(input));
+
+        let error_cases = [
+            (
+                "partial(fn(x) { x }, 1, 2)",
+                "partial received 2 arguments for a function with 1 parameters",
+            ),
+            (
+                "partial(max, 1)",
+                "partial expects a function with a fixed number of parameters",
+            ),
+            (
+                "partial(5, 1)",
+                "expected a FUNCTION argument, but received an INTEGER",
+            ),
+            (
+                "partial(fn(x, y) { x + y }, 1)(1, 2)",
+                "incorrect number of arguments supplied, expected: 1, supplied 2",
+            ),
+            (
+                "partial(compose(len, len), 1, 2)",
+                "partial received 2 arguments for a function with 1 parameters",
+            ),
+        ];
+
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_statement_hook() {
+        use crate::object::{environment::Environment, settings::Settings};
+        use std::{cell::RefCell, rc::Rc};
+
+        let visited = Rc::new(RefCell::new(Vec::new()));
+        let recorder = visited.clone();
+        let env = Environment::new_with_settings(Settings {
+            statement_hook: Some(RefCell::new(Box::new(move |line, column, env| {
+                recorder
+                    .borrow_mut()
+                    .push((line, column, env.get("a").is_some()));
+            }))),
+            ..Default::default()
+        });
+
+        let input = "let x = 1;
+let f = fn(a) {
+    let b = a + x;
+    b * 2
+};
+f(2);
+while (x < 2) { x = x + 1; }";
+        helper_test_null(helper_test_eval_with_env(input, env));
+
+        let expected = [
+            (1, 1, false),
+            (2, 1, false),
+            (6, 1, false),
+            (3, 5, true),
+            (4, 5, true),
+            (7, 1, false),
+            (7, 17, false),
+        ];
+        assert_eq!(*visited.borrow(), expected);
+    }
+
+    #[test]
+    fn test_evaluation_count() {
+        use crate::object::{environment::Environment, settings::Settings};
+        use std::cell::Cell;
+
+        let count_evaluations = |input: &str| {
+            let env = Environment::new_with_settings(Settings {
+                evaluation_count: Some(Cell::new(0)),
+                ..Default::default()
+            });
+            helper_test_eval_with_env(input, env.clone());
+            env.settings().evaluation_count.as_ref().unwrap().get()
+        };
+
+        let trivial = count_evaluations("1");
+        let small_loop = count_evaluations("let i = 0; while (i < 10) { i = i + 1; }");
+        let large_loop = count_evaluations("let i = 0; while (i < 100) { i = i + 1; }");
+
+        assert!(trivial > 0);
+        assert!(small_loop > trivial);
+        assert!(large_loop > small_loop * 5);
+
+        let env = Environment::new();
+        helper_test_eval_with_env("1 + 2", env.clone());
+        assert!(env.settings().evaluation_count.is_none());
+    }
+
+    #[test]
+    fn test_execution_budget() {
+        use crate::object::{environment::Environment, settings::Settings};
+        use std::cell::Cell;
+
+        let with_budget = |budget: u64| {
+            Environment::new_with_settings(Settings {
+                execution_budget: Some(Cell::new(budget)),
+                ..Default::default()
+            })
+        };
+
+        let infinite_loops = [
+            "while (true) {}",
+            "loop {}",
+            "let i = 0; while (true) { i = i + 1; }",
+        ];
+        for input in infinite_loops {
+            let evaluated = helper_test_eval_with_env(input, with_budget(10_000));
+            helper_test_error(evaluated, "execution budget exhausted");
+        }
+
+        let env = with_budget(10_000);
+        let evaluated =
+            helper_test_eval_with_env("let i = 0; while (i < 10) { i = i + 1; }; i", env.clone());
+        helper_test_integer_obj(evaluated, 10);
+        assert!(env.settings().execution_budget.as_ref().unwrap().get() > 0);
+
+        let evaluated = helper_test_eval_with_env("1 + 2", with_budget(2));
+        helper_test_error(evaluated, "execution budget exhausted");
+    }
+
+    #[test]
+    fn test_let_array_destructuring() {
+        let input = "let [a, b, c] = [1, 2, 3]; [c, b, a]";
+        helper_test_array_of_integers(helper_test_eval(input), &[3, 2, 1]);
+
+        let input = "
+            let swap = fn(a, b) { [b, a] }
+            let [x, y] = swap(1, 9)
+            x - y
+        ";
+        helper_test_integer_obj(helper_test_eval(input), 8);
+
+        let input = "let [first] = [5, 6, 7]; first";
+        helper_test_integer_obj(helper_test_eval(input), 5);
+
+        let input = "let f = fn() { let [x, y] = [1, 2]; x + y }; f()";
+        helper_test_integer_obj(helper_test_eval(input), 3);
+
+        let input = "let x = 10; let f = fn() { let [x] = [1]; x }; f() + x";
+        helper_test_integer_obj(helper_test_eval(input), 11);
+
+        let error_cases = [
+            (
+                "let [a, b, c] = [1, 2]; a",
+                "cannot destructure 3 names from an array of 2 elements",
+            ),
+            (
+                "let [a] = 5",
+                "expected an ARRAY to destructure, but received an INTEGER",
+            ),
+            ("let [a] = b", "identifier not found: b"),
+        ];
+
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_let_hash_map_destructuring() {
+        let input = r#"
+            let person = {"name": "alice", "age": 30, "city": "paris"};
+            let {name, age} = person;
+            [name, age]
+        "#;
+        let AllObjects::ArrayObj(arr) = helper_test_eval(input).expect(EXPECTED_ARRAY) else {
+            panic!("{}", EXPECTED_ARRAY);
+        };
+        let elements = arr.elements.borrow();
+        helper_test_string_literal(Some(elements[0].clone()), "alice");
+        helper_test_integer_obj(Some(elements[1].clone()), 30);
+
+        let input = r#"let {email} = {"name": "bob"}; email"#;
+        helper_test_null(helper_test_eval(input));
+
+        let input = r#"let {x} = {1: "one"}; x"#;
+        helper_test_null(helper_test_eval(input));
+
+        helper_test_error(
+            helper_test_eval("let {a} = [1];"),
+            "expected a HASH_MAP to destructure, but received an ARRAY",
+        );
+    }
+
+    #[test]
+    fn test_spread() {
+        let input = "let add = fn(a, b) { a + b }; add(...[1, 2])";
+        helper_test_integer_obj(helper_test_eval(input), 3);
+
+        let input = "let add3 = fn(a, b, c) { a + b + c }; let xs = [2, 3]; add3(1, ...xs)";
+        helper_test_integer_obj(helper_test_eval(input), 6);
+
+        let input = "let xs = [1, 2]; [0, ...xs, 9, ...[], ...xs]";
+        helper_test_array_of_integers(helper_test_eval(input), &[0, 1, 2, 9, 1, 2]);
+
+        let input = "max(...[4, 8, 2])";
+        helper_test_integer_obj(helper_test_eval(input), 8);
+
+        // the elements are shared, the same as when indexing the array
+        let input = "let inner = [1]; let xs = [...[inner]]; push(xs[0], 2); len(inner)";
+        helper_test_integer_obj(helper_test_eval(input), 2);
+
+        let error_cases = [
+            (
+                "let add = fn(a, b) { a + b }; add(...[1])",
+                "incorrect number of arguments supplied, expected: 2, supplied 1",
+            ),
+            (
+                "[...5]",
+                "expected an ARRAY to spread, but received an INTEGER",
+            ),
+            (
+                r#"len(..."abc")"#,
+                "expected an ARRAY to spread, but received a STRING",
+            ),
+            ("[...missing]", "identifier not found: missing"),
+        ];
+
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_in_operator() {
+        let test_cases = [
+            (r#""a" in {"a": 1, "b": 2}"#, true),
+            (r#""c" in {"a": 1, "b": 2}"#, false),
+            (r#"1 in {1: null}"#, true),
+            ("2 in [1, 2, 3]", true),
+            ("5 in [1, 2, 3]", false),
+            ("[1] in [[1], [2]]", true),
+            (r#""ell" in "hello""#, true),
+            (r#""" in "hello""#, true),
+            (r#""xyz" in "hello""#, false),
+            ("1 + 1 in [2] == true", true),
+            ("!(4 in [1, 2])", true),
+        ];
+
+        for tc in test_cases {
+            helper_test_boolean_obj(helper_test_eval(tc.0), tc.1);
+        }
+
+        let error_cases = [
+            (r#"1 in "123""#, "type mismatch: INTEGER in STRING"),
+            ("1 in 2", "unknown operator: INTEGER in INTEGER"),
+            ("x in [1]", "identifier not found: x"),
+        ];
+
+        for tc in error_cases {
+            helper_test_error(helper_test_eval(tc.0), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_strict_truthiness() {
+        use crate::object::{environment::Environment, settings::Settings};
+
+        let strict_env = || {
+            Environment::new_with_settings(Settings {
+                strict_truthiness: true,
+                ..Default::default()
+            })
+        };
+
+        // input, default mode, strict mode
+        let test_cases = [
+            ("if (0) { 1 } else { 2 }", 1, 2),
+            ("if (5) { 1 } else { 2 }", 1, 1),
+            (r#"if ("") { 1 } else { 2 }"#, 1, 2),
+            (r#"if ("a") { 1 } else { 2 }"#, 1, 1),
+            ("if ([]) { 1 } else { 2 }", 1, 2),
+            ("if ([0]) { 1 } else { 2 }", 1, 1),
+            ("if ({}) { 1 } else { 2 }", 1, 2),
+            ("if (!0) { 1 } else { 2 }", 2, 1),
+            ("count([0, 1, 2], fn(x) { x })", 3, 2),
+        ];
+
+        for tc in test_cases {
+            helper_test_integer_obj(helper_test_eval(tc.0), tc.1);
+            helper_test_integer_obj(helper_test_eval_with_env(tc.0, strict_env()), tc.2);
+        }
+
+        let input = "let n = 3; let i = 0; while (n) { n = n - 1; i = i + 1; }; i";
+        helper_test_integer_obj(helper_test_eval_with_env(input, strict_env()), 3);
+    }
+
+    #[test]
+    fn test_null_coalescing() {
+        let test_cases = [
+            (r#"let m = {"a": 1}; m["missing"] ?? 0"#, 0),
+            (r#"let m = {"a": 1}; m["a"] ?? 0"#, 1),
+            ("null ?? null ?? 3", 3),
+            ("false ?? 1; 0 ?? 1", 0),
+            ("[][0:0] ?? 2; 5 ?? 2 + 1", 5),
+        ];
+
+        for tc in test_cases {
+            helper_test_integer_obj(helper_test_eval(tc.0), tc.1);
+        }
+
+        // the right operand is not evaluated when the left one is present
+        let input =
+            "let calls = 0; let f = fn() { calls = calls + 1; 9 }; let x = 4 ?? f(); [x, calls]";
+        helper_test_array_of_integers(helper_test_eval(input), &[4, 0]);
+
+        let input =
+            "let calls = 0; let f = fn() { calls = calls + 1; 9 }; let x = null ?? f(); [x, calls]";
+        helper_test_array_of_integers(helper_test_eval(input), &[9, 1]);
+
+        helper_test_boolean_obj(helper_test_eval("false ?? true"), false);
+        helper_test_error(
+            helper_test_eval("missing ?? 1"),
+            "identifier not found: missing",
+        );
+        helper_test_error(
+            helper_test_eval("null ?? missing"),
+            "identifier not found: missing",
+        );
+    }
+
+    #[test]
+    fn test_optional_index() {
+        helper_test_null(helper_test_eval(r#"null?["k"]"#));
+        helper_test_null(helper_test_eval("null?[0:2]"));
+
+        let input = r#"let m = {"a": {"b": 2}}; m?["a"]?["b"]"#;
+        helper_test_integer_obj(helper_test_eval(input), 2);
+
+        let input = r#"let m = {"a": {"b": 2}}; m["x"]?["b"]"#;
+        helper_test_null(helper_test_eval(input));
+
+        let input = r#"let m = {"a": {"b": 2}}; m["x"]?["b"] ?? 0"#;
+        helper_test_integer_obj(helper_test_eval(input), 0);
+
+        let input = "let arr = [1, 2, 3]; arr?[1:3]";
+        helper_test_array_of_integers(helper_test_eval(input), &[2, 3]);
+
+        // the index is not evaluated when the left side is null
+        let input = "let calls = 0; let f = fn() { calls = calls + 1; 0 }; null?[f()]; calls";
+        helper_test_integer_obj(helper_test_eval(input), 0);
+
+        helper_test_error(
+            helper_test_eval(r#"null["k"]"#),
+            "expected an INTEGER argument, but received a STRING",
+        );
+        helper_test_error(
+            helper_test_eval("5?[0]"),
+            "expected an ARRAY or a STRING argument, but received an INTEGER",
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_line() {
+        use super::helpers;
+        use crate::object::environment::Environment;
+
+        // string literals can't contain double quotes, so the line is passed in as a variable
+        let eval_line = |line: &str| {
+            let env = Environment::new();
+            let line = helpers::get_string_object_for_value(line.to_string());
+            env.set("line".to_string(), line);
+            helper_test_eval_with_env("parse_csv_line(line)", env)
+        };
+
+        let test_cases = [
+            ("a,b,c", vec!["a", "b", "c"]),
+            ("a,,c,", vec!["a", "", "c", ""]),
+            ("", vec![""]),
+            (r#"1,"Doe, John",3"#, vec!["1", "Doe, John", "3"]),
+            (r#""say ""hi""",x"#, vec![r#"say "hi""#, "x"]),
+            (r#""""#, vec![""]),
+            (r#"a"b,c"#, vec![r#"a"b"#, "c"]),
+        ];
+        for tc in test_cases {
+            let AllObjects::ArrayObj(arr) = eval_line(tc.0).expect(EXPECTED_ARRAY) else {
+                panic!("{}", EXPECTED_ARRAY);
+            };
+            let elements = arr.elements.borrow();
+            assert_eq!(elements.len(), tc.1.len(), "input: {}", tc.0);
+            for (element, expected) in elements.iter().zip(tc.1) {
+                helper_test_string_literal(Some(element.clone()), expected);
+            }
+        }
+
+        helper_test_error(
+            eval_line(r#"a,"b"#),
+            "unterminated quoted field in the CSV line",
+        );
+        helper_test_error(
+            eval_line(r#""a"b,c"#),
+            "expected a comma after a quoted CSV field, but found 'b'",
+        );
+    }
+
+    #[test]
+    fn test_from_chars() {
+        let test_cases = [
+            (r#"from_chars(["h", "i"])"#, "hi"),
+            (r#"from_chars(["ab", "", "c"])"#, "abc"),
+            ("from_chars([])", ""),
+        ];
+        for tc in test_cases {
+            helper_test_string_literal(helper_test_eval(tc.0), tc.1);
+        }
+
+        helper_test_error(
+            helper_test_eval(r#"from_chars(["a", 1])"#),
+            "expected STRING elements, but found an INTEGER",
+        );
+        helper_test_error(
+            helper_test_eval(r#"from_chars("hi")"#),
+            "expected an ARRAY argument, but received a STRING",
+        );
+    }
+
+    #[test]
+    fn test_json_stringify() {
+        let test_cases = [
+            ("json_stringify(5)", "5"),
+            ("json_stringify(-12)", "-12"),
+            ("json_stringify(true)", "true"),
+            ("json_stringify(null)", "null"),
+            (r#"json_stringify("hi")"#, r#""hi""#),
+            (r#"json_stringify("a\b")"#, r#""a\\b""#),
+            ("json_stringify(\"a\tb\nc\")", r#""a\tb\nc""#),
+            ("json_stringify([])", "[]"),
+            ("json_stringify({})", "{}"),
+            (
+                r#"json_stringify({"b": [1, "two", {"c": null}], "a": true})"#,
+                r#"{"a":true,"b":[1,"two",{"c":null}]}"#,
+            ),
+            (
+                r#"json_stringify([[1, 2], {"k": [false]}])"#,
+                r#"[[1,2],{"k":[false]}]"#,
+            ),
+        ];
+        for tc in test_cases {
+            helper_test_string_literal(helper_test_eval(tc.0), tc.1);
+        }
+
+        helper_test_error(
+            helper_test_eval("json_stringify([1, fn(x) { x }])"),
+            "cannot serialize a FUNCTION to JSON",
+        );
+        helper_test_error(
+            helper_test_eval("json_stringify({1: len})"),
+            "JSON object keys should be strings, but found an INTEGER",
+        );
+        helper_test_error(
+            helper_test_eval(r#"json_stringify({"f": len})"#),
+            "cannot serialize a FUNCTION to JSON",
+        );
+    }
+
+    #[test]
+    fn test_json_parse() {
+        helper_test_integer_obj(helper_test_eval(r#"json_parse("-42")"#), -42);
+        helper_test_boolean_obj(helper_test_eval(r#"json_parse(" true ")"#), true);
+        helper_test_null(helper_test_eval(r#"json_parse("null")"#));
+        helper_test_string_literal(
+            helper_test_eval(r#"json_parse(json_stringify("a\b"))"#),
+            "a\\b",
+        );
+
+        let input = r#"let m = json_parse(json_stringify({"a": [1, {"b": null}], "c": "d"}));
+            [m["a"][0], m["c"], m["a"][1]["b"]]"#;
+        let AllObjects::ArrayObj(arr) = helper_test_eval(input).expect(EXPECTED_ARRAY) else {
+            panic!("{}", EXPECTED_ARRAY);
+        };
+        let elements = arr.elements.borrow();
+        helper_test_integer_obj(Some(elements[0].clone()), 1);
+        helper_test_string_literal(Some(elements[1].clone()), "d");
+        helper_test_null(Some(elements[2].clone()));
+
+        let test_cases = [
+            r#"{"b": [1, 2, {"c": false}], "a": "x"}"#,
+            "[[], {}, -7, null]",
+        ];
+        for tc in test_cases {
+            let input = format!(
+                "let s = json_stringify({}); json_stringify(json_parse(s)) == s",
+                tc
+            );
+            helper_test_boolean_obj(helper_test_eval(&input), true);
+        }
+
+        let test_cases = [
+            ("", "invalid JSON at position 0: unexpected end of input"),
+            ("[1, 2", "invalid JSON at position 5: expected ',' or ']'"),
+            (
+                "{1: 2}",
+                "invalid JSON at position 1: expected a string key",
+            ),
+            ("[1 2]", "invalid JSON at position 3: expected ',' or ']'"),
+            ("[tru]", "invalid JSON at position 1: unexpected literal"),
+            (
+                "1.5",
+                "invalid JSON at position 1: only integer numbers are supported",
+            ),
+            (
+                "01",
+                "invalid JSON at position 0: numbers can't have leading zeros",
+            ),
+            (
+                "[] []",
+                "invalid JSON at position 3: unexpected characters after the document",
+            ),
+        ];
+        for tc in test_cases {
+            let input = format!(r#"json_parse("{}")"#, tc.0);
+            helper_test_error(helper_test_eval(&input), tc.1);
+        }
+
+        helper_test_error(
+            helper_test_eval(r#"json_parse("[" * 200000)"#),
+            "invalid JSON at position 128: nesting too deep",
+        );
+    }
+
+    #[test]
+    fn test_assoc_and_dissoc() {
+        let input =
+            r#"let m = {"a": 1}; let n = assoc(m, "b", 2); [len(entries(m)), n["a"], n["b"]]"#;
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 1, 2]);
+
+        let input = r#"let m = {"a": 1}; let alias = m; assoc(m, "a", 5); [m["a"], alias["a"]]"#;
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 1]);
+
+        let input = r#"let m = {"a": 1, "b": 2}; let n = dissoc(m, "a"); [n["b"], m["a"]]"#;
+        helper_test_array_of_integers(helper_test_eval(input), &[2, 1]);
+
+        let input = r#"let m = {"a": 1}; dissoc(m, "a")["a"]"#;
+        helper_test_null(helper_test_eval(input));
+
+        let input = r#"let m = {"a": 1}; dissoc(m, "x")["a"]"#;
+        helper_test_integer_obj(helper_test_eval(input), 1);
+
+        helper_test_error(
+            helper_test_eval(r#"assoc([1], "a", 1)"#),
+            "expected a hash map argument, but received an ARRAY",
+        );
+        helper_test_error(
+            helper_test_eval(r#"dissoc("m", "a")"#),
+            "expected a hash map argument, but received a STRING",
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let input = r#"let m = merge({"a": 1}, {"b": 2}); [len(entries(m)), m["a"], m["b"]]"#;
+        helper_test_array_of_integers(helper_test_eval(input), &[2, 1, 2]);
+
+        let input = r#"let m = merge({"a": 1, "b": 2}, {"b": 3}); [m["a"], m["b"]]"#;
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 3]);
+
+        let input = r#"let a = {"x": 1}; let b = {"x": 2, "y": 3}; merge(a, b);
+            [len(entries(a)), a["x"], len(entries(b)), b["x"]]"#;
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 1, 2, 2]);
+
+        helper_test_error(
+            helper_test_eval(r#"merge({"a": 1}, [1])"#),
+            "expected a hash map argument, but received an ARRAY",
+        );
+        helper_test_error(
+            helper_test_eval(r#"merge(1, {"a": 1})"#),
+            "expected a hash map argument, but received an INTEGER",
+        );
+    }
+
+    #[test]
+    fn test_let_without_value() {
+        helper_test_null(helper_test_eval("let x; x"));
+        helper_test_integer_obj(helper_test_eval("let x\nx ?? 3"), 3);
+
+        let input = "let x; if (true) { x = 5 }; x";
+        helper_test_integer_obj(helper_test_eval(input), 5);
+
+        let input = "let sign; let n = -4; if (n < 0) { sign = -1 } else { sign = 1 }; sign * n";
+        helper_test_integer_obj(helper_test_eval(input), 4);
+    }
+
+    #[test]
+    fn test_block_expression() {
+        let input = "let y = { let t = 1; t + 1; }; y";
+        helper_test_integer_obj(helper_test_eval(input), 2);
+
+        let input = "let z = {\n  let a = 3\n  a * 2\n}\nz";
+        helper_test_integer_obj(helper_test_eval(input), 6);
+
+        // the names declared in the block are local to it
+        let input = "let t = 5; let y = { let t = 1; t }; [t, y]";
+        helper_test_array_of_integers(helper_test_eval(input), &[5, 1]);
+
+        let input = "let t = 5; { t = 7 }; t";
+        helper_test_integer_obj(helper_test_eval(input), 7);
+
+        let input =
+            "let f = fn(x) { let r = { if (x > 1) { return 10 }; 0 }; r + 1 }; [f(0), f(2)]";
+        helper_test_array_of_integers(helper_test_eval(input), &[1, 10]);
+
+        let input = r#"{ {"k": 5} }["k"]"#;
+        helper_test_integer_obj(helper_test_eval(input), 5);
+
+        let AllObjects::HashMap(m) = helper_test_eval("{}").expect("expected a hash map") else {
+            panic!("expected a hash map");
+        };
+        assert!(m.map.borrow().is_empty());
+
+        helper_test_error(
+            helper_test_eval(r#"{ let a = 1; a + "b" }"#),
+            "type mismatch: INTEGER + STRING",
+        );
+    }
+
+    #[test]
+    fn test_repr() {
+        use crate::object::Object;
+
+        let evaluated = helper_test_eval(r#""a""#).expect(EXPECTED_OBJECT);
+        assert_eq!(evaluated.inspect(), "a");
+        assert_eq!(evaluated.repr(), r#""a""#);
+
+        let evaluated = helper_test_eval(r#""tab\there""#).expect(EXPECTED_OBJECT);
+        assert_eq!(evaluated.inspect(), "tab\there");
+        assert_eq!(evaluated.repr(), r#""tab\there""#);
+
+        let evaluated = helper_test_eval(r#"{"k": "v"}"#).expect(EXPECTED_OBJECT);
+        assert_eq!(evaluated.repr(), r#"{ "k":"v" }"#);
+
+        let input = r#"["a", 1, ["b c"]]"#;
+        for input in ["5", "true", "null", "fn(x) { x }", input] {
+            let evaluated = helper_test_eval(input).expect(EXPECTED_OBJECT);
+            assert_eq!(evaluated.repr(), evaluated.inspect(), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_partial_ord() {
+        use std::cmp::Ordering;
+
+        let test_cases = [
+            ("1", "2", Some(Ordering::Less)),
+            ("-5", "-5", Some(Ordering::Equal)),
+            (r#""b""#, r#""a""#, Some(Ordering::Greater)),
+            (r#""ab""#, r#""abc""#, Some(Ordering::Less)),
+            ("[1, 2]", "[1, 3]", Some(Ordering::Less)),
+            ("[1, 2]", "[1]", Some(Ordering::Greater)),
+            ("1", r#""1""#, None),
+            ("[1]", r#"["a"]"#, None),
+            ("true", "false", None),
+            ("[1, true]", "[1, null]", None),
+            // equal values are always ordered as equal
+            ("true", "true", Some(Ordering::Equal)),
+            ("null", "null", Some(Ordering::Equal)),
+            ("[true, [null]]", "[true, [null]]", Some(Ordering::Equal)),
+        ];
+        for tc in test_cases {
+            let left = helper_test_eval(tc.0).expect(EXPECTED_OBJECT);
+            let right = helper_test_eval(tc.1).expect(EXPECTED_OBJECT);
+            assert_eq!(left.partial_cmp(&right), tc.2, "{} vs {}", tc.0, tc.1);
+        }
+
+        let (one, two) = (
+            helper_test_eval("1").unwrap(),
+            helper_test_eval("2").unwrap(),
+        );
+        assert!(one < two && two >= one);
+    }
+
+    #[test]
+    fn test_display() {
+        let test_cases = [
+            ("5", "5"),
+            (r#""hi""#, "hi"),
+            ("true", "true"),
+            ("null", "null"),
+            (r#"[1, "a", [null]]"#, r#"[1, "a", [null]]"#),
+            ("fn(x) { x }", "fn(x) {\n    x;\n}"),
+            ("len", "len"),
+            (r#"1 + "a""#, "Error: type mismatch: INTEGER + STRING"),
+        ];
+        for tc in test_cases {
+            let evaluated = helper_test_eval(tc.0).expect(EXPECTED_OBJECT);
+            assert_eq!(format!("{}", evaluated), tc.1);
+            assert_eq!(evaluated.to_string(), tc.1);
+        }
+    }
+
+    #[test]
+    fn test_nested_string_inspect() {
+        use super::helpers;
+        use crate::object::{environment::Environment, Object};
+
+        let test_cases = [
+            (r#"["a", "b"]"#, r#"["a", "b"]"#),
+            (r#"[["a"], "b c", 1]"#, r#"[["a"], "b c", 1]"#),
+            (r#"{"k": ["v"]}"#, r#"{ "k":["v"] }"#),
+            (r#"{1: "1"}"#, r#"{ 1:"1" }"#),
+        ];
+        for tc in test_cases {
+            let evaluated = helper_test_eval(tc.0).expect(EXPECTED_OBJECT);
+            assert_eq!(evaluated.inspect(), tc.1);
+        }
+
+        // string literals can't contain quotes, so the value is passed in as a variable
+        let env = Environment::new();
+        let quoted = helpers::get_string_object_for_value(r#"say "hi""#.to_string());
+        env.set("quoted".to_string(), quoted);
+        let evaluated = helper_test_eval_with_env("[quoted]", env).expect(EXPECTED_ARRAY);
+        assert_eq!(evaluated.inspect(), r#"["say \"hi\""]"#);
+
+        // bare strings are still shown without quotes
+        helper_test_string_literal(helper_test_eval(r#"inspect_sorted("a b")"#), "a b");
+    }
+
+    #[test]
+    fn test_integer_bounds() {
+        helper_test_integer_obj(helper_test_eval("max_int()"), i64::MAX);
+        helper_test_integer_obj(helper_test_eval("min_int()"), i64::MIN);
+        helper_test_integer_obj(helper_test_eval("max_int() + min_int()"), -1);
+        helper_test_error(
+            helper_test_eval("max_int(1)"),
+            "incorrect number of arguments supplied, expected: 0, supplied 1",
+        );
+    }
+
+    #[test]
+    fn test_gcd_and_lcm() {
+        let test_cases = [
+            ("gcd(12, 18)", 6),
+            ("gcd(18, 12)", 6),
+            ("gcd(-12, 18)", 6),
+            ("gcd(7, 13)", 1),
+            ("gcd(0, 5)", 5),
+            ("gcd(0, 0)", 0),
+            ("lcm(4, 6)", 12),
+            ("lcm(-4, 6)", 12),
+            ("lcm(7, 1)", 7),
+            ("lcm(0, 5)", 0),
+            ("lcm(0, 0)", 0),
+            ("gcd(min_int(), 6)", 2),
+        ];
+        for tc in test_cases {
+            helper_test_integer_obj(helper_test_eval(tc.0), tc.1);
+        }
+
+        for input in [
+            "lcm(max_int(), 2)",
+            "gcd(min_int(), 0)",
+            "lcm(min_int(), 1)",
+        ] {
+            helper_test_error(helper_test_eval(input), "integer overflow");
+        }
+        helper_test_error(
+            helper_test_eval(r#"gcd(4, "6")"#),
+            "expected an INTEGER argument, but received a STRING",
+        );
+    }
+
+    #[test]
+    fn test_bit_introspection() {
+        let test_cases = [
+            ("bit_count(7)", 3),
+            ("bit_count(0)", 0),
+            ("bit_count(-1)", 64),
+            ("leading_zeros(1)", 63),
+            ("leading_zeros(0)", 64),
+            ("leading_zeros(-1)", 0),
+            ("trailing_zeros(8)", 3),
+            ("trailing_zeros(0)", 64),
+            ("trailing_zeros(7)", 0),
+        ];
+        for tc in test_cases {
+            helper_test_integer_obj(helper_test_eval(tc.0), tc.1);
+        }
+
+        for input in [
+            r#"bit_count("7")"#,
+            r#"leading_zeros("7")"#,
+            r#"trailing_zeros("7")"#,
+        ] {
+            helper_test_error(
+                helper_test_eval(input),
+                "expected an INTEGER argument, but received a STRING",
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        use crate::object::objects::ErrorKind;
+
+        let test_cases = [
+            ("5 + true;", ErrorKind::TypeMismatch),
+            ("-true", ErrorKind::UnknownOperator),
+            ("foobar", ErrorKind::UnknownIdentifier),
+            ("[1, 2][5]", ErrorKind::IndexOutOfRange),
+            ("10 / 0", ErrorKind::DivisionByZero),
+            ("len(1, 2)", ErrorKind::InvalidArgument),
+            ("len(1)", ErrorKind::TypeMismatch),
+            ("sum([9223372036854775807, 1])", ErrorKind::Overflow),
+            ("break;", ErrorKind::InvalidControlFlow),
+        ];
+
+        for tc in test_cases {
+            match helper_test_eval(tc.0).expect(EXPECTED_ERROR) {
+                AllObjects::Error(e) => assert_eq!(e.kind, tc.1, "input: {}", tc.0),
+                _ => panic!("{}", EXPECTED_ERROR),
+            }
+        }
+
+        assert_eq!(ErrorKind::DivisionByZero.to_string(), "DivisionByZero");
+    }
+
+    #[test]
+    fn test_match_expressions() {
+        let describe = r#"let describe = fn(v) {
+            match v {
+                [a, b] => a + b,
+                {name} => "name: " + name,
+                0 => "zero",
+                "x" => "an x",
+                n => len(v),
+            }
+        };"#;
+        let test_cases = [
+            ("describe([1, 2])", "3"),
+            ("describe([1, 2, 3])", "3"),
+            (r#"describe({"name": "bob", "age": 3})"#, "name: bob"),
+            (r#"describe({"age": 3})"#, "1"),
+            ("describe(0)", "zero"),
+            (r#"describe("x")"#, "an x"),
+            (r#"describe("four")"#, "4"),
+        ];
+        for tc in test_cases {
+            use crate::object::Object;
+            let input = format!("{} {}", describe, tc.0);
+            let evaluated = helper_test_eval(&input).expect(EXPECTED_OBJECT);
+            assert_eq!(evaluated.inspect(), tc.1, "input: {}", tc.0);
+        }
+
+        helper_test_null(helper_test_eval("match 5 { 1 => 1, [a] => a }"));
+        // the names bound by a pattern don't leak out of the arm
+        helper_test_error(
+            helper_test_eval("let r = match [1] { [a] => a }; a"),
+            "identifier not found: a",
+        );
+        helper_test_integer_obj(helper_test_eval("let a = 7; match [1] { [a] => a } + a"), 8);
+        // errors in the subject or in value patterns are propagated
+        helper_test_error(
+            helper_test_eval("match missing { n => n }"),
+            "identifier not found: missing",
+        );
+        helper_test_error(
+            helper_test_eval("match 1 { -missing => 1 }"),
+            "identifier not found: missing",
+        );
+    }
+
+    #[test]
+    fn test_globals() {
+        let input = r#"let a = 10; let b = "foo"; globals();"#;
+        let map = match helper_test_eval(input).expect(EXPECTED_HASH_MAP) {
+            AllObjects::HashMap(v) => v,
+            _ => panic!("{}", EXPECTED_HASH_MAP),
+        };
+        assert_eq!(map.map.borrow().len(), 2);
+
+        let input = r#"let a = 10; let b = "foo"; let g = globals(); g["a"]"#;
+        let evaluated = helper_test_eval(input);
+        helper_test_integer_obj(evaluated, 10);
+
+        let input = r#"let a = 10; let b = "foo"; let g = globals(); g["b"]"#;
+        let evaluated = helper_test_eval(input);
+        helper_test_string_literal(evaluated, "foo");
+
+        // outer scopes are included when called within a function
+        let input = "let a = 10; let f = fn(b) { let g = globals(); g[\"a\"] + g[\"b\"] }; f(5);";
+        let evaluated = helper_test_eval(input);
+        helper_test_integer_obj(evaluated, 15);
+        // builtins read the scope of the caller
+        let input = "let f = fn() { let local = 3; globals()[\"local\"] }; f();";
+        helper_test_integer_obj(helper_test_eval(input), 3);
+    }
+}
+
+#[cfg(test)]
+mod test_helpers {
+    use super::eval::eval;
+    use crate::{
+        lexer::Lexer,
+        object::{environment::Environment, AllObjects},
+        parser,
+    };
+    use std::rc::Rc;
+
+    pub fn helper_test_eval(input: &str) -> Option<AllObjects> {
+        helper_test_eval_with_env(input, Environment::new())
+    }
+
+    pub fn helper_test_eval_with_env(input: &str, env: Rc<Environment>) -> Option<AllObjects> {
+        let l = Lexer::new(input);
+        let mut p = parser::Parser::new(l);
+        let program = p.parse_program();
+
+        eval(program.make_node(), env)
+    }
+
+    pub fn helper_test_integer_obj(obj: Option<AllObjects>, expected: i64) {
+        let AllObjects::Integer(obj) = obj.expect(EXPECTED_OBJECT) else {
+            panic!("{}", EXPECTED_INT_OBJECT);
+        };
+        assert_eq!(obj.value, expected);
+    }
+
+    pub fn helper_test_string_literal(obj: Option<AllObjects>, expected: &str) {
+        let AllObjects::StringObj(obj) = obj.expect(EXPECTED_OBJECT) else{
+            panic!("{}", EXPECTED_STRING_OBJECT);
+        };
+        assert_eq!(*obj.value, expected);
+    }
+
+    pub fn helper_test_boolean_obj(obj: Option<AllObjects>, expected: bool) {
+        let AllObjects::Boolean(obj) = obj.expect(EXPECTED_OBJECT) else {
+            panic!("{}", EXPECTED_BOOLEAN_OBJECT);
+        };
+        assert_eq!(obj.value, expected);
+    }
+
+    pub fn helper_test_array_of_integers(obj: Option<AllObjects>, expected: &[i64]) {
+        let AllObjects::ArrayObj(obj) = obj.expect(EXPECTED_OBJECT) else {
+            panic!("{}", EXPECTED_ARRAY);
+        };
+        let elements = obj.elements.borrow();
+        assert_eq!(elements.len(), expected.len());
+        for (element, value) in elements.iter().zip(expected) {
+            helper_test_integer_obj(Some(element.clone()), *value);
+        }
     }
 
     pub fn helper_test_null(obj: Option<AllObjects>) {
@@ -0,0 +1,79 @@
+use crate::{
+    lexer::Lexer,
+    parser::{ParseError, Parser},
+};
+
+/// Parses the program and renders it back in a canonical format, with each statement on its own
+/// line, consistent spacing around operators and blocks indented by four spaces.
+///
+/// Formatting the output again yields the same output. Returns the parser errors, if the input
+/// cannot be parsed.
+pub fn format_source(input: &str) -> Result<String, Vec<ParseError>> {
+    let mut p = Parser::new(Lexer::new(input));
+    let program = p.parse_program();
+
+    if !p.parse_errors().is_empty() {
+        return Err(p.parse_errors().to_vec());
+    }
+
+    let mut out = String::new();
+    for stmt in program.statements {
+        out.push_str(&stmt.to_terminated_string());
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_source;
+
+    #[test]
+    fn test_format_source() {
+        let input = r#"let add=fn(a,b){a+b};
+            let   result = add(1,2*3) ;
+            if(result>5){print("big")}else{print("small")}"#;
+
+        let expected = r#"let add = fn(a, b) {
+    (a + b);
+};
+let result = add(1, (2 * 3));
+if (result > 5) {
+    print("big");
+} else {
+    print("small");
+};
+"#;
+        assert_eq!(format_source(input).unwrap(), expected);
+
+        // the contents of multi-line strings are not indented
+        let input = "fn() { if (true) { \"a\nb\" } }";
+        let expected = "fn() {\n    if (true) {\n        \"a\nb\";\n    };\n};\n";
+        assert_eq!(format_source(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_format_source_is_idempotent() {
+        let inputs = [
+            "let x = 5; x = x + 1; -x;",
+            r#"let m = {"b": [1, 2], "a": fn(x) { return x * 2 }}; m["a"](3);"#,
+            "let i = 0; while (i < 10) { if (i == 5) { break; } i = i + 1 }",
+            "loop { let arr = [1, 2, 3]; print(arr[0:2]); break len(arr); }",
+            "let f = fn() { fn(y) { if (!y) { null } else { y } } }; f()(true);",
+            "fn() { \"a\nb\" }",
+            "let f = fn() { if (true) { let s = \"a\n  b\n\"; s } };",
+        ];
+
+        for input in inputs {
+            let formatted = format_source(input).unwrap();
+            assert_eq!(format_source(&formatted).unwrap(), formatted);
+        }
+    }
+
+    #[test]
+    fn test_format_source_errors() {
+        let errors = format_source("let = 5;").unwrap_err();
+        assert!(!errors.is_empty());
+    }
+}
@@ -3,7 +3,12 @@ pub const LET: &str = "let";
 pub const IF: &str = "if";
 pub const ELSE: &str = "else";
 pub const WHILE: &str = "while";
+pub const LOOP: &str = "loop";
+pub const BREAK: &str = "break";
 pub const RETURN: &str = "return";
 pub const TRUE: &str = "true";
 pub const FALSE: &str = "false";
 pub const NULL: &str = "null";
+pub const IN: &str = "in";
+pub const MATCH: &str = "match";
+pub const WHEN: &str = "when";
@@ -3,6 +3,7 @@ pub mod token;
 mod tokenizer;
 
 /// Lexer is the structure that will tokenize a given input string
+#[derive(Clone)]
 pub struct Lexer {
     /// input for the lexer (TODO: make it a readable interface)
     input: Vec<char>,
@@ -15,4 +16,10 @@ pub struct Lexer {
 
     /// current character under examination
     ch: char,
+
+    /// line of the current character, which is used to let newlines terminate statements
+    line: u32,
+
+    /// position in input of the first character of the current line
+    line_start: usize,
 }
@@ -4,6 +4,14 @@ use super::keywords;
 pub struct Token {
     pub token_type: TokenType,
     pub literal: String,
+    /// the line on which the token starts, beginning from 1. Tokens that are not read from the input have line 0
+    pub line: u32,
+    /// the column at which the token starts on its line, beginning from 1. Tokens that are not read from the
+    /// input have column 0
+    pub column: u32,
+    /// the line on which the token ends, which differs from `line` only for string literals spanning
+    /// several lines
+    pub end_line: u32,
 }
 
 /// Represents the UNICODE null character
@@ -28,8 +36,12 @@ pub enum TokenType {
     Slash,
     Lt,
     Gt,
+    LtEq,
+    GtEq,
     Eq,
     NotEq,
+    NullCoalesce,
+    FatArrow,
 
     // Delimiters
     Comma,
@@ -41,7 +53,10 @@ pub enum TokenType {
     Rbrace,
     Lbracket,
     Rbracket,
+    OptionalLbracket,
     Colon,
+    Spread,
+    Dot,
 
     // Keywords
     Function,
@@ -49,10 +64,15 @@ pub enum TokenType {
     If,
     Else,
     While,
+    Loop,
+    Break,
     Return,
     True,
     False,
     Null,
+    In,
+    Match,
+    When,
 }
 
 /// A helper function to return an EOF token for initializing the parser
@@ -65,6 +85,9 @@ pub fn new_token<T: ToString>(token_type: TokenType, literal: T) -> Token {
     Token {
         token_type,
         literal: literal.to_string(),
+        line: 0,
+        column: 0,
+        end_line: 0,
     }
 }
 
@@ -79,9 +102,14 @@ pub fn look_up_identifier(ident: &str) -> TokenType {
         ELSE => TokenType::Else,
         RETURN => TokenType::Return,
         WHILE => TokenType::While,
+        LOOP => TokenType::Loop,
+        BREAK => TokenType::Break,
         TRUE => TokenType::True,
         FALSE => TokenType::False,
         NULL => TokenType::Null,
+        IN => TokenType::In,
+        MATCH => TokenType::Match,
+        WHEN => TokenType::When,
         _ => TokenType::Ident,
     }
 }
@@ -94,6 +122,11 @@ mod tests {
     fn test_look_up_identifier() {
         assert_eq!(TokenType::Function, look_up_identifier("fn"));
         assert_eq!(TokenType::Let, look_up_identifier("let"));
+        assert_eq!(TokenType::Loop, look_up_identifier("loop"));
+        assert_eq!(TokenType::Break, look_up_identifier("break"));
+        assert_eq!(TokenType::In, look_up_identifier("in"));
+        assert_eq!(TokenType::Match, look_up_identifier("match"));
+        assert_eq!(TokenType::When, look_up_identifier("when"));
         assert_eq!(TokenType::Ident, look_up_identifier("my name is khan"));
     }
 }
@@ -9,6 +9,8 @@ impl Lexer {
             position: 0,
             read_position: 0,
             ch: NULL_CHAR,
+            line: 1,
+            line_start: 0,
         }
     }
 
@@ -26,11 +28,15 @@ impl Lexer {
         }
 
         // get the matching token
-        let tok = match self.ch {
+        let (line, column) = (self.line, (self.position - self.line_start + 1) as u32);
+        let mut tok = match self.ch {
             '=' => {
                 if self.peek_char() == '=' {
                     self.read_char();
                     new_token(TokenType::Eq, "==")
+                } else if self.peek_char() == '>' {
+                    self.read_char();
+                    new_token(TokenType::FatArrow, "=>")
                 } else {
                     new_token(TokenType::Assign, self.ch)
                 }
@@ -51,14 +57,44 @@ impl Lexer {
             }
             '*' => new_token(TokenType::Asterisk, self.ch),
             '/' => new_token(TokenType::Slash, self.ch),
-            '<' => new_token(TokenType::Lt, self.ch),
-            '>' => new_token(TokenType::Gt, self.ch),
+            '<' => {
+                if self.peek_char() == '=' {
+                    self.read_char();
+                    new_token(TokenType::LtEq, "<=")
+                } else {
+                    new_token(TokenType::Lt, self.ch)
+                }
+            }
+            '>' => {
+                if self.peek_char() == '=' {
+                    self.read_char();
+                    new_token(TokenType::GtEq, ">=")
+                } else {
+                    new_token(TokenType::Gt, self.ch)
+                }
+            }
             '{' => new_token(TokenType::Lbrace, self.ch),
             '}' => new_token(TokenType::Rbrace, self.ch),
             '"' => new_token(TokenType::String, self.read_string()),
             '[' => new_token(TokenType::Lbracket, self.ch),
             ']' => new_token(TokenType::Rbracket, self.ch),
             ':' => new_token(TokenType::Colon, self.ch),
+            '?' if self.peek_char() == '?' => {
+                self.read_char();
+                new_token(TokenType::NullCoalesce, "??")
+            }
+            '?' if self.peek_char() == '[' => {
+                self.read_char();
+                new_token(TokenType::OptionalLbracket, "?[")
+            }
+            '.' if self.peek_char() == '.'
+                && self.input.get(self.read_position + 1) == Some(&'.') =>
+            {
+                self.read_char();
+                self.read_char();
+                new_token(TokenType::Spread, "...")
+            }
+            '.' => new_token(TokenType::Dot, self.ch),
             NULL_CHAR => new_token(TokenType::Eof, NULL_CHAR),
             _ => {
                 if is_letter(self.ch) {
@@ -74,14 +110,24 @@ impl Lexer {
             }
         };
 
+        // the current character is the last one of the token, so it's on the line the token ends
+        tok.end_line = self.line;
+
         // read the next character
         self.read_char();
 
+        tok.line = line;
+        tok.column = column;
         tok
     }
 
     /// Sets the next character and advances the position in the input
     fn read_char(&mut self) {
+        if self.ch == '\n' {
+            self.line += 1;
+            self.line_start = self.read_position;
+        }
+
         if self.read_position >= self.input.len() {
             self.ch = NULL_CHAR;
         } else {
@@ -183,6 +229,129 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_token_lines() {
+        let input = "let x = 5;\n\nlet s = \"multi\nline\";\n  x";
+        let mut l = Lexer::new(input);
+
+        let expected_lines = [1, 1, 1, 1, 1, 3, 3, 3, 3, 4, 5, 5];
+        for (i, line) in expected_lines.iter().enumerate() {
+            let tok = l.next_token();
+            assert_eq!(
+                tok.line, *line,
+                "tests[{}] - line wrong for {:?}",
+                i, tok.literal
+            );
+        }
+
+        // only the multi-line string ends on a different line than it starts
+        let mut l = Lexer::new(input);
+        let expected_end_lines = [1, 1, 1, 1, 1, 3, 3, 3, 4, 4, 5, 5];
+        for (i, end_line) in expected_end_lines.iter().enumerate() {
+            let tok = l.next_token();
+            assert_eq!(
+                tok.end_line, *end_line,
+                "tests[{}] - end line wrong for {:?}",
+                i, tok.literal
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_token_for_question_marks() {
+        let input = "a ?? 0 ??? m?[1]";
+        let mut l = Lexer::new(input);
+
+        let test_cases = [
+            new_token(Ident, "a"),
+            new_token(NullCoalesce, "??"),
+            new_token(Int, "0"),
+            new_token(NullCoalesce, "??"),
+            new_token(Illegal, '?'),
+            new_token(Ident, "m"),
+            new_token(OptionalLbracket, "?["),
+            new_token(Int, "1"),
+            new_token(Rbracket, ']'),
+            new_token(Eof, NULL_CHAR),
+        ];
+
+        for (i, tt) in test_cases.iter().enumerate() {
+            let tok = l.next_token();
+            assert_eq!(tt.token_type, tok.token_type, "tests[{}]", i);
+            assert_eq!(tt.literal, tok.literal, "tests[{}]", i);
+        }
+    }
+
+    #[test]
+    fn test_next_token_for_match() {
+        let input = "match x { [a] => a, _ => 0 } == =>";
+        let mut l = Lexer::new(input);
+
+        let test_cases = [
+            new_token(Match, "match"),
+            new_token(Ident, "x"),
+            new_token(Lbrace, '{'),
+            new_token(Lbracket, '['),
+            new_token(Ident, "a"),
+            new_token(Rbracket, ']'),
+            new_token(FatArrow, "=>"),
+            new_token(Ident, "a"),
+            new_token(Comma, ','),
+            new_token(Ident, "_"),
+            new_token(FatArrow, "=>"),
+            new_token(Int, "0"),
+            new_token(Rbrace, '}'),
+            new_token(Eq, "=="),
+            new_token(FatArrow, "=>"),
+            new_token(Eof, NULL_CHAR),
+        ];
+
+        for (i, tt) in test_cases.iter().enumerate() {
+            let tok = l.next_token();
+            assert_eq!(tt.token_type, tok.token_type, "tests[{}]", i);
+            assert_eq!(tt.literal, tok.literal, "tests[{}]", i);
+        }
+    }
+
+    #[test]
+    fn test_next_token_for_spread() {
+        let input = "[...xs] .. .";
+        let mut l = Lexer::new(input);
+
+        let test_cases = [
+            new_token(Lbracket, '['),
+            new_token(Spread, "..."),
+            new_token(Ident, "xs"),
+            new_token(Rbracket, ']'),
+            new_token(Dot, '.'),
+            new_token(Dot, '.'),
+            new_token(Dot, '.'),
+            new_token(Eof, NULL_CHAR),
+        ];
+
+        for (i, tt) in test_cases.iter().enumerate() {
+            let tok = l.next_token();
+            assert_eq!(tt.token_type, tok.token_type, "tests[{}]", i);
+            assert_eq!(tt.literal, tok.literal, "tests[{}]", i);
+        }
+    }
+
+    #[test]
+    fn test_token_columns() {
+        let input = "let x = 5;\n\nlet s = \"multi\nline\";\n  x";
+        let mut l = Lexer::new(input);
+
+        let expected_columns = [1, 5, 7, 9, 10, 1, 5, 7, 9, 6, 3, 4];
+        for (i, column) in expected_columns.iter().enumerate() {
+            let tok = l.next_token();
+            assert_eq!(
+                tok.column, *column,
+                "tests[{}] - column wrong for {:?}",
+                i, tok.literal
+            );
+        }
+    }
+
     #[test]
     fn test_next_token_source_code() {
         let input = r#"let five = 5;
@@ -201,6 +370,7 @@ mod tests {
                 }
                 10 == 10; 
                 10 != 9;
+                5 <= 10 >= 5;
                 "foobar"
                 "foo bar"
                 [1, 2];
@@ -283,6 +453,12 @@ mod tests {
             new_token(NotEq, "!="),
             new_token(Int, "9"),
             new_token(Semicolon, ';'),
+            new_token(Int, "5"),
+            new_token(LtEq, "<="),
+            new_token(Int, "10"),
+            new_token(GtEq, ">="),
+            new_token(Int, "5"),
+            new_token(Semicolon, ';'),
             new_token(String, "foobar"),
             new_token(String, "foo bar"),
             new_token(Lbracket, "["),
@@ -1,23 +1,83 @@
 // for testing stdout in print function
 #![feature(internal_output_capture)]
 
-mod ast;
+pub mod ast;
 mod evaluator;
+mod formatter;
 mod lexer;
-mod object;
+pub mod object;
 mod parser;
 mod repl;
 
-pub use object::environment::Environment;
-pub use repl::{execute_program, start_repl};
-use std::{error::Error, fs, io::Write};
+pub use formatter::format_source;
+pub use lexer::Lexer;
+pub use object::{
+    environment::Environment,
+    settings::{OutputLimit, Settings},
+};
+pub use parser::{ParseError, Parser};
+pub use repl::{execute_program, run_program, run_timed, start_repl, Args, Termination};
+use std::{
+    error::Error,
+    fs,
+    io::{Read, Write},
+    rc::Rc,
+};
 
-/// Read and execute the given input file
-pub fn read_file<U: Write>(given_path: String, output: &mut U) -> Result<(), Box<dyn Error>> {
+/// Read and execute the given input file, returning how the program ended
+pub fn read_file<U: Write>(
+    given_path: String,
+    output: &mut U,
+) -> Result<Termination, Box<dyn Error>> {
+    read_file_with_env(given_path, output, Environment::new())
+}
+
+/// Read and execute the given input file in the given environment, which lets the caller configure
+/// the settings of the program, such as its arguments.
+pub fn read_file_with_env<U: Write>(
+    given_path: String,
+    output: &mut U,
+    env: Rc<Environment>,
+) -> Result<Termination, Box<dyn Error>> {
     let file_path = std::path::Path::new(&given_path);
     let content = fs::read(file_path)?;
     let input = String::from_utf8(content)?;
-    execute_program(&input, output, Environment::new())?;
+    let termination = execute_program(skip_shebang(&input), output, env)?;
+
+    Ok(termination)
+}
+
+/// Read the whole reader as a program and execute it, returning how the program ended. This lets
+/// programs be piped in through stdin, without entering the REPL.
+pub fn read_program<T: Read, U: Write>(
+    input: &mut T,
+    output: &mut U,
+) -> Result<Termination, Box<dyn Error>> {
+    read_program_with_env(input, output, Environment::new())
+}
+
+/// Read the whole reader as a program and execute it in the given environment
+pub fn read_program_with_env<T: Read, U: Write>(
+    input: &mut T,
+    output: &mut U,
+    env: Rc<Environment>,
+) -> Result<Termination, Box<dyn Error>> {
+    let mut program = String::new();
+    input.read_to_string(&mut program)?;
+    let termination = execute_program(skip_shebang(&program), output, env)?;
+
+    Ok(termination)
+}
+
+/// Skips a `#!` line at the very start of a script, such as `#!/usr/bin/env interpreter`. The line
+/// break is kept, so that the line numbers of the program don't change.
+fn skip_shebang(input: &str) -> &str {
+    if !input.starts_with("#!") {
+        return input;
+    }
 
-    Ok(())
+    match input.find('\n') {
+        Some(end) => &input[end..],
+        None => "",
+    }
 }
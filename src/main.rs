@@ -1,17 +1,37 @@
-use interpreter_lib::{read_file, start_repl};
+use clap::Parser;
+use interpreter_lib::{
+    read_file_with_env, read_program_with_env, run_timed, start_repl, Args, Environment, Settings,
+    Termination,
+};
 use std::io::{self, BufReader};
 
 fn main() {
     let mut writer = io::stdout();
 
-    let mut args = std::env::args();
-    if args.len() > 1 {
-        let file_path = args.nth(1).unwrap();
-        if let Err(e) = read_file(file_path, &mut writer) {
-            eprintln!("{}", e);
-            std::process::exit(1);
+    let args = Args::parse();
+    if let Some(file_path) = args.file {
+        let env = Environment::new_with_settings(Settings {
+            program_args: args.script_args,
+            ..Default::default()
+        });
+
+        // a `-` path reads the program from stdin, as in `cat prog.mok | interpreter -`
+        let result = run_timed(args.time, &mut io::stderr(), || {
+            if file_path == "-" {
+                read_program_with_env(&mut io::stdin(), &mut writer, env)
+            } else {
+                read_file_with_env(file_path, &mut writer, env)
+            }
+        });
+        match result.unwrap_or_else(|e| Err(e.into())) {
+            Ok(Termination::Completed) => std::process::exit(0),
+            Ok(Termination::Aborted) => std::process::exit(1),
+            Ok(Termination::Exited(code)) => std::process::exit(code as i32),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
         }
-        std::process::exit(0);
     }
 
     let mut reader = BufReader::new(io::stdin());
@@ -1,21 +1,32 @@
-use super::AllObjects;
+use super::{
+    objects::{BuiltinFunctionObj, ParamsType},
+    settings::Settings,
+    AllObjects,
+};
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 /// Environment is what is used to keep track of values by associating them with an identifier.
 ///
 /// It includes a store to collect variables of the main scope and an outer variable to keep track
-/// of a function variables.
+/// of a function variables. The runtime settings are shared by all the enclosed environments.
 pub struct Environment {
     store: RefCell<HashMap<String, AllObjects>>,
     outer: Option<Rc<Environment>>,
+    settings: Rc<Settings>,
 }
 
 impl Environment {
-    /// Creates a new Environment
+    /// Creates a new Environment with the default settings
     pub fn new() -> Rc<Environment> {
+        Environment::new_with_settings(Settings::default())
+    }
+
+    /// Creates a new Environment with the given settings
+    pub fn new_with_settings(settings: Settings) -> Rc<Environment> {
         let env = Environment {
             store: RefCell::new(HashMap::new()),
             outer: None,
+            settings: Rc::new(settings),
         };
         Rc::new(env)
     }
@@ -25,11 +36,17 @@ impl Environment {
         let mut new_env = Environment {
             store: RefCell::new(HashMap::new()),
             outer: None,
+            settings: outer.settings.clone(),
         };
         new_env.outer = Some(outer);
         Rc::new(new_env)
     }
 
+    /// Returns the runtime settings of the environment
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
     /// Returns a clone of the `Object` corresponding to the `identifier` after recursively
     /// examining all the chained scopes.
     ///
@@ -59,6 +76,25 @@ impl Environment {
         value
     }
 
+    /// Registers a native function provided by the host, which programs can then call by its name.
+    ///
+    /// The function receives the environment holding the arguments, keyed by the parameter names,
+    /// followed by the environment of the caller. Registered functions take precedence over the
+    /// builtin functions of the same name.
+    pub fn register(
+        &self,
+        name: &str,
+        parameters: ParamsType,
+        func: fn(Rc<Environment>, Rc<Environment>) -> AllObjects,
+    ) {
+        let builtin = BuiltinFunctionObj {
+            fn_name: name.to_string(),
+            parameters,
+            func,
+        };
+        self.set(name.to_string(), AllObjects::BuiltinFunction(builtin));
+    }
+
     /// Replaces an existing var with the passed value and return the same value if it was replaced successfully and
     /// return None if the scope chain doesn't have the key present.
     ///
@@ -83,12 +119,43 @@ impl Environment {
         None
     }
 
+    /// Returns the name-value pairs of the variables defined in this scope, sorted by their names.
+    ///
+    /// Variables of the outer scopes are not included; use `outer` to walk through the enclosing scopes.
+    pub fn iter(&self) -> Vec<(String, AllObjects)> {
+        let mut pairs = self
+            .store
+            .borrow()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<Vec<_>>();
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        pairs
+    }
+
+    /// Returns the enclosing environment, or None if this is the outermost scope
+    pub fn outer(&self) -> Option<Rc<Environment>> {
+        self.outer.clone()
+    }
+
     /// Returns a list of all variables in the environment. Useful for variadic functions.
     pub fn all_vars(&self) -> Vec<String> {
         let mut v = self.store.borrow().keys().cloned().collect::<Vec<_>>();
         v.sort();
         v
     }
+
+    /// Returns a sorted list of all variables visible from this environment, including the ones defined
+    /// in the outer scopes. Shadowed names are only listed once.
+    pub fn visible_vars(&self) -> Vec<String> {
+        let mut v = self.all_vars();
+        if let Some(ref outer) = self.outer {
+            v.extend(outer.visible_vars());
+        }
+        v.sort();
+        v.dedup();
+        v
+    }
 }
 
 #[cfg(test)]
@@ -109,6 +176,26 @@ mod tests {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn test_iter_and_outer() {
+        let int = |value| AllObjects::Integer(Integer { value });
+        let outer = Environment::new();
+        outer.set("z".to_string(), int(3));
+
+        let env = Environment::new_enclosed_environment(outer.clone());
+        env.set("y".to_string(), int(2));
+        env.set("x".to_string(), int(1));
+        env.set("y".to_string(), int(4));
+
+        let pairs = env.iter();
+        assert!(pairs == vec![("x".to_string(), int(1)), ("y".to_string(), int(4))]);
+
+        let parent = env.outer().expect("expected an outer environment");
+        assert!(Rc::ptr_eq(&parent, &outer));
+        assert!(parent.iter() == vec![("z".to_string(), int(3))]);
+        assert!(parent.outer().is_none());
+    }
+
     #[test]
     fn test_replace_outer() {
         let val = AllObjects::Integer(Integer { value: 12 });
@@ -120,6 +207,7 @@ mod tests {
 
         let env = Environment {
             store: RefCell::new(HashMap::new()),
+            settings: outer.settings.clone(),
             outer: Some(outer),
         };
 
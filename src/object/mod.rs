@@ -1,10 +1,23 @@
-use std::fmt::{self, Display};
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    fmt::{self, Display},
+    rc::Rc,
+};
 
 pub mod environment;
 pub mod objects;
+pub mod settings;
 
 pub trait Object {
     fn inspect(&self) -> String;
+
+    /// Returns an unambiguous form of the object, which is the same as `inspect` except that a
+    /// string is quoted and shows its escape sequences. Strings nested inside arrays and hash maps are
+    /// always rendered this way, so only top-level strings differ.
+    fn repr(&self) -> String {
+        self.inspect()
+    }
 }
 
 /// This is useful when doing just type comparisons disregarding underlying value
@@ -16,10 +29,12 @@ pub enum ObjectType {
     Null,
     Error,
     Return,
+    Break,
     Function,
     BuiltInFunction,
     Array,
     HashMap,
+    StringBuilder,
 }
 
 impl Display for ObjectType {
@@ -31,10 +46,12 @@ impl Display for ObjectType {
             ObjectType::Null => "NULL",
             ObjectType::Error => "ERROR",
             ObjectType::Return => "RETURN",
+            ObjectType::Break => "BREAK",
             ObjectType::Function => "FUNCTION",
             ObjectType::BuiltInFunction => "BUILTIN_FUNCTION",
             ObjectType::Array => "ARRAY",
             ObjectType::HashMap => "HASH_MAP",
+            ObjectType::StringBuilder => "STRING_BUILDER",
         };
         write!(f, "{}", out)
     }
@@ -50,11 +67,17 @@ pub enum AllObjects {
     Boolean(objects::Boolean),
     Null(objects::Null),
     Error(objects::Error),
+    FatalError(objects::FatalError),
+    /// Signals that the program should end with the given status code
+    ExitSignal(i64),
     ReturnValue(Box<AllObjects>),
+    BreakValue(Box<AllObjects>),
     Function(objects::FunctionObj),
     BuiltinFunction(objects::BuiltinFunctionObj),
+    NativeClosure(objects::NativeClosureObj),
     ArrayObj(objects::ArrayObj),
     HashMap(objects::HashMapObj),
+    StringBuilder(objects::StringBuilderObj),
 }
 
 impl Object for AllObjects {
@@ -65,11 +88,60 @@ impl Object for AllObjects {
             Self::Boolean(v) => v.inspect(),
             Self::Null(v) => v.inspect(),
             Self::Error(v) => v.inspect(),
+            Self::FatalError(v) => v.inspect(),
+            Self::ExitSignal(v) => format!("exit({})", v),
             Self::ReturnValue(v) => v.inspect(),
+            Self::BreakValue(v) => v.inspect(),
             Self::Function(v) => v.inspect(),
             Self::BuiltinFunction(v) => v.inspect(),
+            Self::NativeClosure(v) => v.inspect(),
             Self::ArrayObj(v) => v.inspect(),
             Self::HashMap(v) => v.inspect(),
+            Self::StringBuilder(v) => v.inspect(),
+        }
+    }
+
+    fn repr(&self) -> String {
+        match self {
+            Self::StringObj(v) => v.repr(),
+            Self::ReturnValue(v) => v.repr(),
+            Self::BreakValue(v) => v.repr(),
+            v => v.inspect(),
+        }
+    }
+}
+
+/// Formats the object the same way as `inspect`, so that host code can use `{}` and `to_string`
+impl Display for AllObjects {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+/// Integers and strings are ordered by their values, and arrays are ordered lexicographically by their elements.
+///
+/// Equal values are always ordered as equal. `None` is returned for any other pair of values, including values
+/// of different types.
+impl PartialOrd for AllObjects {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            return Some(Ordering::Equal);
+        }
+
+        match (self, other) {
+            (Self::Integer(l), Self::Integer(r)) => Some(l.value.cmp(&r.value)),
+            (Self::StringObj(l), Self::StringObj(r)) => Some(l.value.cmp(&r.value)),
+            (Self::ArrayObj(l), Self::ArrayObj(r)) => {
+                let (l, r) = (l.elements.borrow(), r.elements.borrow());
+                for (l_elem, r_elem) in l.iter().zip(r.iter()) {
+                    match l_elem.partial_cmp(r_elem)? {
+                        Ordering::Equal => continue,
+                        other => return Some(other),
+                    }
+                }
+                Some(l.len().cmp(&r.len()))
+            }
+            _ => None,
         }
     }
 }
@@ -82,20 +154,63 @@ impl AllObjects {
             Self::Boolean(_) => ObjectType::Boolean,
             Self::Null(_) => ObjectType::Null,
             Self::Error(_) => ObjectType::Error,
+            // fatal errors and exit signals are propagated in the same way as the other errors
+            Self::FatalError(_) | Self::ExitSignal(_) => ObjectType::Error,
             Self::ReturnValue(_) => ObjectType::Return,
+            Self::BreakValue(_) => ObjectType::Break,
             Self::Function(_) => ObjectType::Function,
             Self::BuiltinFunction(_) => ObjectType::Function,
+            Self::NativeClosure(_) => ObjectType::Function,
             Self::ArrayObj(_) => ObjectType::Array,
             Self::HashMap(_) => ObjectType::HashMap,
+            Self::StringBuilder(_) => ObjectType::StringBuilder,
         }
     }
 
-    pub fn new_error(message: &str) -> Self {
+    pub fn new_error(kind: objects::ErrorKind, message: &str) -> Self {
         Self::Error(objects::Error {
             message: message.to_string(),
+            kind,
+        })
+    }
+
+    pub fn new_fatal_error(message: &str) -> Self {
+        Self::FatalError(objects::FatalError {
+            message: message.to_string(),
         })
     }
 
+    /// Returns a copy of the object, where arrays and hash maps (including the nested ones) and string
+    /// builders get their own storage instead of sharing it with the original. All the other objects
+    /// are cloned as they are.
+    // a copied key hashes the same as the original one, since its contents are left unchanged
+    #[allow(clippy::mutable_key_type)]
+    pub fn deep_copy(&self) -> Self {
+        match self {
+            Self::ArrayObj(v) => {
+                let elements = v.elements.borrow().iter().map(|e| e.deep_copy()).collect();
+                Self::ArrayObj(objects::ArrayObj {
+                    elements: Rc::new(RefCell::new(elements)),
+                })
+            }
+            Self::HashMap(v) => {
+                let map = v
+                    .map
+                    .borrow()
+                    .iter()
+                    .map(|(k, v)| (k.deep_copy(), v.deep_copy()))
+                    .collect();
+                Self::HashMap(objects::HashMapObj {
+                    map: Rc::new(RefCell::new(map)),
+                })
+            }
+            Self::StringBuilder(v) => Self::StringBuilder(objects::StringBuilderObj {
+                value: Rc::new(RefCell::new(v.value.borrow().clone())),
+            }),
+            other => other.clone(),
+        }
+    }
+
     pub fn is_integer(&self) -> bool {
         self.object_type() == ObjectType::Integer
     }
@@ -115,4 +230,16 @@ impl AllObjects {
     pub fn is_error(&self) -> bool {
         self.object_type() == ObjectType::Error
     }
+
+    pub fn is_array(&self) -> bool {
+        self.object_type() == ObjectType::Array
+    }
+
+    pub fn is_hash_map(&self) -> bool {
+        self.object_type() == ObjectType::HashMap
+    }
+
+    pub fn is_function(&self) -> bool {
+        self.object_type() == ObjectType::Function
+    }
 }
@@ -22,6 +22,15 @@ impl Object for StringObj {
     fn inspect(&self) -> String {
         self.value.replace("\\n", "\n").replace("\\t", "\t")
     }
+
+    fn repr(&self) -> String {
+        let escaped = self
+            .value
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+            .replace('\t', "\\t");
+        format!("\"{}\"", escaped)
+    }
 }
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -47,6 +56,28 @@ impl Object for Null {
 #[derive(PartialEq, Eq, Hash, Clone)]
 pub struct Error {
     pub message: String,
+    pub kind: ErrorKind,
+}
+
+/// The category of an error, which allows programs to distinguish errors without parsing the message
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum ErrorKind {
+    TypeMismatch,
+    UnknownOperator,
+    UnknownIdentifier,
+    InvalidArgument,
+    IndexOutOfRange,
+    DivisionByZero,
+    Overflow,
+    InvalidControlFlow,
+    PermissionDenied,
+    LimitExceeded,
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
 }
 
 impl Object for Error {
@@ -55,6 +86,18 @@ impl Object for Error {
     }
 }
 
+/// An error which aborts the whole program, regardless of where it was raised
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct FatalError {
+    pub message: String,
+}
+
+impl Object for FatalError {
+    fn inspect(&self) -> String {
+        format!("Panic: {}", self.message)
+    }
+}
+
 /// Includes the name of the function and the definition of the function which wraps around in RC to be
 /// clonable to fit the API of the other clonable objects.
 ///
@@ -93,7 +136,7 @@ impl Object for FunctionObj {
             .collect::<Vec<String>>()
             .join(", ");
 
-        format!("fn({}){{\n{}\n}}", params, self.body)
+        format!("fn({}) {}", params, self.body.to_braced_string())
     }
 }
 
@@ -101,13 +144,19 @@ impl Object for FunctionObj {
 pub struct BuiltinFunctionObj {
     pub fn_name: String,
     pub parameters: ParamsType,
-    pub func: fn(Rc<Environment>) -> AllObjects,
+    /// receives the environment holding the arguments, followed by the environment of the caller
+    pub func: fn(Rc<Environment>, Rc<Environment>) -> AllObjects,
 }
 
 #[derive(PartialEq, Eq, Hash, Clone)]
 pub enum ParamsType {
     Fixed(Vec<String>),
     Variadic,
+    /// Accepts any number of arguments, as long as there are at least the given number of arguments
+    Minimum(usize),
+    /// Accepts the required parameters followed by any number of the optional parameters, in order.
+    /// The optional parameters which are not supplied are left unbound.
+    Optional(Vec<String>, Vec<String>),
 }
 
 impl Object for BuiltinFunctionObj {
@@ -116,6 +165,48 @@ impl Object for BuiltinFunctionObj {
     }
 }
 
+/// A function implemented natively which carries the values it closes over, such as the functions
+/// returned by `compose` and `partial`.
+#[derive(Clone)]
+pub struct NativeClosureObj {
+    /// the name of the builtin which created the closure
+    pub fn_name: String,
+    /// the number of arguments the closure accepts
+    pub arity: usize,
+    pub captured: Vec<AllObjects>,
+    /// receives the captured values and the arguments, followed by the environment of the caller
+    pub func: fn(&[AllObjects], Vec<AllObjects>, Rc<Environment>) -> AllObjects,
+}
+
+impl PartialEq for NativeClosureObj {
+    fn eq(&self, other: &Self) -> bool {
+        self.fn_name == other.fn_name && self.captured == other.captured
+    }
+}
+
+impl Eq for NativeClosureObj {}
+
+impl Hash for NativeClosureObj {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.fn_name.hash(state);
+        self.captured.hash(state);
+    }
+}
+
+impl Object for NativeClosureObj {
+    /// Shows the closure as the call which created it, e.g. `partial(add, 1)`
+    fn inspect(&self) -> String {
+        let captured = self
+            .captured
+            .iter()
+            .map(|v| v.repr())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        format!("{}({})", self.fn_name, captured)
+    }
+}
+
 #[derive(Clone)]
 pub struct ArrayObj {
     pub elements: Rc<RefCell<Vec<AllObjects>>>,
@@ -142,13 +233,35 @@ impl Object for ArrayObj {
             self.elements
                 .borrow()
                 .iter()
-                .map(|v| v.inspect())
+                .map(|v| v.repr())
                 .collect::<Vec<String>>()
                 .join(", ")
         )
     }
 }
 
+/// A mutable string which can be appended to in place, so that building a string piece by piece
+/// doesn't copy it on every step, as concatenating with `+` does.
+#[derive(PartialEq, Eq, Clone)]
+pub struct StringBuilderObj {
+    pub value: Rc<RefCell<String>>,
+}
+
+impl Hash for StringBuilderObj {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.borrow().hash(state)
+    }
+}
+
+impl Object for StringBuilderObj {
+    fn inspect(&self) -> String {
+        let value = StringObj {
+            value: Rc::new(self.value.borrow().clone()),
+        };
+        format!("string_builder({})", value.repr())
+    }
+}
+
 #[derive(Clone)]
 pub struct HashMapObj {
     pub map: Rc<RefCell<HashMap<AllObjects, AllObjects>>>,
@@ -173,7 +286,7 @@ impl Object for HashMapObj {
         let binding = self.map.borrow();
         let out = binding
             .iter()
-            .map(|(k, v)| format!("{}:{}", k.inspect(), v.inspect()))
+            .map(|(k, v)| format!("{}:{}", k.repr(), v.repr()))
             .collect::<Vec<String>>()
             .join(", ");
 
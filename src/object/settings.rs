@@ -0,0 +1,84 @@
+use super::environment::Environment;
+use std::{
+    cell::{Cell, RefCell},
+    io::Write,
+    rc::Rc,
+};
+
+/// A callback which receives the line and the column of a statement along with the environment it's
+/// evaluated in
+pub type StatementHook = RefCell<Box<dyn FnMut(u32, u32, &Rc<Environment>)>>;
+
+/// A writer which receives the diagnostics written by `debug`
+pub type DebugWriter = RefCell<Box<dyn Write>>;
+
+/// Settings that control the runtime behaviour of the evaluator.
+///
+/// A single instance is shared between an environment and all the environments enclosed by it.
+#[derive(Default)]
+pub struct Settings {
+    /// When enabled, arrays and hash maps are deep copied by `let` statements and assignments, so that
+    /// each variable owns its own copy instead of sharing the same storage.
+    pub copy_on_assign: bool,
+    /// When enabled, builtins which access the host system, such as reading environment variables,
+    /// return an error instead.
+    pub sandboxed: bool,
+    /// When enabled, `0`, empty strings and empty arrays and hash maps are falsy, in addition to
+    /// `false` and `null`.
+    pub strict_truthiness: bool,
+    /// When set, the hook is called before each statement is evaluated, which lets a debugger pause
+    /// the program or inspect its variables. The hook is not called for statements evaluated by the
+    /// hook itself.
+    pub statement_hook: Option<StatementHook>,
+    /// When set, counts the number of nodes evaluated, which gives an estimate of the cost of a program.
+    /// The count can be read after the program has run.
+    pub evaluation_count: Option<Cell<u64>>,
+    /// When set, caps the total number of bytes written by `print`, after which `print` returns an
+    /// error instead of writing, so that a runaway loop can't flood the output.
+    pub output_limit: Option<OutputLimit>,
+    /// When set, holds the number of evaluation steps the program may still take. Each evaluated node
+    /// and block takes a step, and once the budget runs out, evaluation stops with an error. This
+    /// bounds the running time of untrusted programs, such as ones with infinite loops.
+    pub execution_budget: Option<Cell<u64>>,
+    /// The command-line arguments passed to the program, which it can read with `args()`
+    pub program_args: Vec<String>,
+    /// When set, caps the number of elements of an array, or bytes of a string, that a single call to
+    /// builtins such as `repeat` or `pad_left` may create, so that a program can't crash the process
+    /// by requesting an enormous allocation.
+    pub allocation_limit: Option<usize>,
+    /// When set, `debug` writes to this writer instead of stderr
+    pub debug_writer: Option<DebugWriter>,
+}
+
+/// Keeps track of the bytes written by `print` against a maximum
+pub struct OutputLimit {
+    max_bytes: usize,
+    written: Cell<usize>,
+}
+
+impl OutputLimit {
+    /// Creates a limit that allows at most `max_bytes` bytes of output
+    pub fn new(max_bytes: usize) -> Self {
+        OutputLimit {
+            max_bytes,
+            written: Cell::new(0),
+        }
+    }
+
+    /// Returns the number of bytes written so far
+    pub fn written(&self) -> usize {
+        self.written.get()
+    }
+
+    /// Records the given number of bytes as written and returns true, if they fit within the limit.
+    /// Otherwise nothing is recorded and false is returned.
+    pub fn try_write(&self, bytes: usize) -> bool {
+        match self.written.get().checked_add(bytes) {
+            Some(total) if total <= self.max_bytes => {
+                self.written.set(total);
+                true
+            }
+            _ => false,
+        }
+    }
+}
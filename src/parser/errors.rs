@@ -0,0 +1,33 @@
+use crate::lexer::token::Token;
+use std::fmt::Display;
+
+/// An error found while parsing, along with the position of the token it was found at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// the line of the offending token, beginning from 1
+    pub line: u32,
+    /// the column of the offending token on its line, beginning from 1
+    pub column: u32,
+}
+
+impl ParseError {
+    /// Returns a new error positioned at the given token
+    pub fn at(message: String, token: &Token) -> Self {
+        ParseError {
+            message,
+            line: token.line,
+            column: token.column,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
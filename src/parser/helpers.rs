@@ -1,4 +1,4 @@
-use super::{program::Parser, Precedence};
+use super::{errors::ParseError, program::Parser, Precedence};
 use crate::lexer::token::TokenType;
 
 use std::mem;
@@ -34,14 +34,87 @@ impl Parser {
     pub fn peek_error(&mut self, token_type: TokenType) {
         let msg = format!(
             "expected next token to be {:?}, got {:?} instead",
-            token_type, self.peek_token
+            token_type, self.peek_token.token_type
         );
-        self.errors.push(msg);
+        self.errors.push(ParseError::at(msg, &self.peek_token));
     }
 
     pub fn no_prefix_parse_fn_error(&mut self, token_type: TokenType) {
         let msg = format!("no prefix parse function for {:?} found", token_type);
-        self.errors.push(msg);
+        self.errors.push(ParseError::at(msg, &self.current_token));
+    }
+
+    pub fn chained_comparison_error(&mut self, operator: &str) {
+        let msg = format!(
+            "chained comparisons are not supported, found {} followed by {}; compare each pair separately",
+            operator, self.peek_token.literal
+        );
+        self.errors.push(ParseError::at(msg, &self.peek_token));
+    }
+
+    /// Checks if the peek token is on a new line while the current expression is not nested
+    /// inside parentheses or brackets, in which case the newline terminates the statement. The line
+    /// is compared with where the current token ends, since a string literal can span several lines.
+    /// A line starting with an infix operator continues the statement instead.
+    pub fn newline_terminates_statement(&self) -> bool {
+        self.nesting == 0
+            && self.peek_token.line > self.current_token.end_line
+            && !self.peek_token_continues_expression()
+    }
+
+    /// Checks if the peek token is an infix operator, such as `+` or `==`, which carries the
+    /// expression over to the next line. `(` and `[` are excluded since they also start grouped
+    /// expressions and array literals.
+    fn peek_token_continues_expression(&self) -> bool {
+        !matches!(
+            self.peek_token.token_type,
+            TokenType::Lparen | TokenType::Lbracket
+        ) && Parser::infix_parse_function(&self.peek_token.token_type).is_some()
+    }
+
+    /// Checks if the let statement ends right after its name, either with a semicolon, the end of
+    /// the input or a terminating newline
+    pub fn let_value_omitted(&self) -> bool {
+        self.peek_token_is(&TokenType::Semicolon)
+            || self.peek_token_is(&TokenType::Eof)
+            || self.newline_terminates_statement()
+    }
+
+    /// Checks if the current `{` opens a hash literal rather than a block expression, by scanning
+    /// ahead for the `:` of the first entry. The scan stops at the first `;` or `}` which isn't
+    /// nested inside the entry, in which case it's a block.
+    pub fn hash_literal_ahead(&self) -> bool {
+        use TokenType::*;
+
+        if self.peek_token_is(&Rbrace) {
+            return true;
+        }
+
+        let mut lexer = self.l.clone();
+        let mut token_type = self.peek_token.token_type.clone();
+        let mut depth = 0;
+        loop {
+            match token_type {
+                Colon if depth == 0 => return true,
+                Semicolon | Rbrace | Let | Return | While | Loop | Break if depth == 0 => {
+                    return false;
+                }
+                Eof => return false,
+                Lparen | Lbracket | OptionalLbracket | Lbrace => depth += 1,
+                Rparen | Rbracket | Rbrace => depth -= 1,
+                _ => {}
+            }
+            token_type = lexer.next_token().token_type;
+        }
+    }
+
+    /// Runs the given parse function with the nesting level incremented, so that newlines inside
+    /// parentheses and brackets do not terminate the statement
+    pub fn nested<T>(&mut self, parse_fn: impl FnOnce(&mut Self) -> T) -> T {
+        self.nesting += 1;
+        let result = parse_fn(self);
+        self.nesting -= 1;
+        result
     }
 
     pub fn peek_precedence(&self) -> Precedence {
@@ -1,11 +1,13 @@
 use crate::lexer::token::TokenType;
 
+mod errors;
 mod helpers;
 mod parse_expressions;
 mod parse_statements;
 mod program;
 mod tracing;
 
+pub use errors::ParseError;
 pub use program::Parser;
 pub static mut TRACING_ENABLED: bool = false;
 
@@ -13,13 +15,14 @@ pub static mut TRACING_ENABLED: bool = false;
 #[derive(PartialEq, Eq, PartialOrd, Debug)]
 pub enum Precedence {
     Lowest = 1,
-    Equals = 2,
-    LessGreater = 3,
-    Sum = 4,
-    Product = 5,
-    Prefix = 6,
-    Call = 7,
-    Index = 8,
+    NullCoalesce = 2,
+    Equals = 3,
+    LessGreater = 4,
+    Sum = 5,
+    Product = 6,
+    Prefix = 7,
+    Call = 8,
+    Index = 9,
 }
 
 impl Precedence {
@@ -28,12 +31,13 @@ impl Precedence {
         use TokenType::*;
 
         match token_type {
-            Eq | NotEq => Equals,
-            Lt | Gt => LessGreater,
+            TokenType::NullCoalesce => Precedence::NullCoalesce,
+            Eq | NotEq | In => Equals,
+            Lt | Gt | LtEq | GtEq => LessGreater,
             Plus | Minus => Sum,
             Slash | Asterisk => Product,
             Lparen => Call,
-            Lbracket => Index,
+            Lbracket | OptionalLbracket | Dot => Index,
             _ => Lowest,
         }
     }
@@ -1,17 +1,17 @@
-use std::collections::HashMap;
-
 use super::{
+    errors::ParseError,
     program::{BoxedExpression, Parser},
     Precedence,
 };
 use crate::ast::expressions::{
-    self, AllExpressions, ArrayLiteral, AssignmentExpression, Boolean, CallExpression,
-    FunctionLiteral, HashLiteral, Identifier, IfExpression, IndexExpression, RangeExpression,
-    StringLiteral,
+    self, AllExpressions, ArrayLiteral, AssignmentExpression, BlockExpression, Boolean,
+    CallExpression, FunctionLiteral, HashLiteral, Identifier, IfExpression, IndexExpression,
+    MatchArm, MatchExpression, MatchPattern, RangeExpression, SpreadExpression, StringLiteral,
 };
 use crate::ast::statements::ExpressionStatement;
 use crate::ast::statements::{AllStatements, BlockStatement};
-use crate::lexer::token::TokenType;
+use crate::lexer::token::{Token, TokenType};
+use std::num::IntErrorKind;
 
 impl Parser {
     /// Parses expression statements.
@@ -31,10 +31,6 @@ impl Parser {
             stmt.expression = self.parse_expression(Precedence::Lowest);
         }
 
-        if self.peek_token_is(&TokenType::Semicolon) {
-            self.next_token();
-        }
-
         self.tracer.un_trace(trace_msg);
         Some(AllStatements::Expression(stmt))
     }
@@ -52,7 +48,10 @@ impl Parser {
 
         let mut left_expr = prefix(self);
 
-        while !self.peek_token_is(&TokenType::Semicolon) && precedence < self.peek_precedence() {
+        while !self.peek_token_is(&TokenType::Semicolon)
+            && !self.newline_terminates_statement()
+            && precedence < self.peek_precedence()
+        {
             let infix = match Parser::infix_parse_function(&self.peek_token.token_type) {
                 Some(v) => v,
                 None => return left_expr,
@@ -106,8 +105,14 @@ pub fn parse_integer_literal(p: &mut Parser) -> BoxedExpression {
     let value = match p.current_token.literal.parse::<i64>() {
         Ok(v) => v,
         Err(e) => {
-            let msg = format!("could not parse as integer: {:?}", e);
-            p.errors.push(msg);
+            let msg = match e.kind() {
+                IntErrorKind::PosOverflow => format!(
+                    "integer literal {} is too large for a 64-bit integer",
+                    p.current_token.literal
+                ),
+                _ => format!("could not parse as integer: {:?}", e),
+            };
+            p.errors.push(ParseError::at(msg, &p.current_token));
             return None;
         }
     };
@@ -150,7 +155,7 @@ pub fn parse_grouped_expression(p: &mut Parser) -> BoxedExpression {
     let trace_msg = p.tracer.trace("parseGroupedExpression");
     p.next_token();
 
-    let expr = p.parse_expression(Precedence::Lowest);
+    let expr = p.nested(|p| p.parse_expression(Precedence::Lowest));
     if !p.expect_peek(TokenType::Rparen) {
         return None;
     }
@@ -189,33 +194,24 @@ pub fn parse_infix_expression(p: &mut Parser, left: BoxedExpression) -> BoxedExp
     };
 
     let precedence = p.current_precedence();
+    let is_comparison = precedence == Precedence::LessGreater;
     p.next_token();
     expression.right = p.parse_expression(precedence);
 
+    // `a < b < c` would otherwise be parsed as `(a < b) < c`, comparing a boolean with the last operand
+    if is_comparison && p.peek_precedence() == Precedence::LessGreater {
+        p.chained_comparison_error(&expression.operator);
+        p.tracer.un_trace(trace_msg);
+        return None;
+    }
+
     p.tracer.un_trace(trace_msg);
     Some(Box::new(AllExpressions::InfixExpression(expression)))
 }
 
 pub fn parse_if_expression(p: &mut Parser) -> BoxedExpression {
     let token_literal = p.current_token.clone();
-
-    if !p.expect_peek(TokenType::Lparen) {
-        return None;
-    }
-
-    p.next_token();
-
-    let condition = p.parse_expression(Precedence::Lowest)?;
-
-    if !p.expect_peek(TokenType::Rparen) {
-        return None;
-    }
-
-    if !p.expect_peek(TokenType::Lbrace) {
-        return None;
-    }
-
-    let consequence = parse_block_statement(p);
+    let (condition, consequence) = parse_condition_and_block(p)?;
 
     let mut alternative = None;
     if p.peek_token_is(&TokenType::Else) {
@@ -236,6 +232,42 @@ pub fn parse_if_expression(p: &mut Parser) -> BoxedExpression {
     Some(Box::new(AllExpressions::IfExpression(if_expr)))
 }
 
+/// Parses `when (condition) { value }`, which evaluates to the value if the condition is truthy and
+/// to null otherwise. It's desugared into an if expression without an alternative, so the value is
+/// only evaluated when the condition holds, unlike the arguments of a builtin.
+pub fn parse_when_expression(p: &mut Parser) -> BoxedExpression {
+    let token = p.current_token.clone();
+    let (condition, consequence) = parse_condition_and_block(p)?;
+
+    Some(Box::new(AllExpressions::IfExpression(IfExpression {
+        token,
+        condition,
+        consequence,
+        alternative: None,
+    })))
+}
+
+/// Parses a parenthesized condition followed by a block, as in `(x > 1) { x }`
+fn parse_condition_and_block(p: &mut Parser) -> Option<(Box<AllExpressions>, BlockStatement)> {
+    if !p.expect_peek(TokenType::Lparen) {
+        return None;
+    }
+
+    p.next_token();
+
+    let condition = p.parse_expression(Precedence::Lowest)?;
+
+    if !p.expect_peek(TokenType::Rparen) {
+        return None;
+    }
+
+    if !p.expect_peek(TokenType::Lbrace) {
+        return None;
+    }
+
+    Some((condition, parse_block_statement(p)))
+}
+
 pub fn parse_function_literal(p: &mut Parser) -> BoxedExpression {
     let token = p.current_token.clone();
 
@@ -243,7 +275,7 @@ pub fn parse_function_literal(p: &mut Parser) -> BoxedExpression {
         return None;
     }
 
-    let parameters = parse_fn_literal_parameters(p)?;
+    let parameters = parse_identifier_list(p, &TokenType::Rparen)?;
 
     p.next_token(); // consumes )
     if !p.expect_peek(TokenType::Lbrace) {
@@ -265,7 +297,7 @@ pub fn parse_call_expression(p: &mut Parser, left: BoxedExpression) -> BoxedExpr
     let token = p.current_token.clone(); // (
     let function = left?;
 
-    let arguments = parse_comma_sep_arguments(p, &TokenType::Rparen)?;
+    let arguments = p.nested(|p| parse_comma_sep_arguments(p, &TokenType::Rparen))?;
     p.next_token(); // consumes )
 
     let expr = CallExpression {
@@ -280,7 +312,7 @@ pub fn parse_call_expression(p: &mut Parser, left: BoxedExpression) -> BoxedExpr
 pub fn parse_array_literal(p: &mut Parser) -> BoxedExpression {
     let token = p.current_token.clone(); // [
 
-    let elements = parse_comma_sep_arguments(p, &TokenType::Rbracket)?;
+    let elements = p.nested(|p| parse_comma_sep_arguments(p, &TokenType::Rbracket))?;
     p.next_token(); // consume ]
 
     let array = AllExpressions::ArrayLiteral(ArrayLiteral { token, elements });
@@ -289,15 +321,16 @@ pub fn parse_array_literal(p: &mut Parser) -> BoxedExpression {
 
 pub fn parse_index_expressions(p: &mut Parser, left: BoxedExpression) -> BoxedExpression {
     let token = p.current_token.clone();
-    p.next_token(); // consume [
+    let optional = p.current_token_is(&TokenType::OptionalLbracket);
+    p.next_token(); // consume [ OR ?[
 
-    let index = p.parse_expression(Precedence::Lowest);
+    let index = p.nested(|p| p.parse_expression(Precedence::Lowest));
     let mut right = None;
 
     if p.peek_token_is(&TokenType::Colon) {
         p.next_token();
         p.next_token();
-        right = p.parse_expression(Precedence::Lowest);
+        right = p.nested(|p| p.parse_expression(Precedence::Lowest));
     }
 
     if !p.expect_peek(TokenType::Rbracket) {
@@ -309,6 +342,7 @@ pub fn parse_index_expressions(p: &mut Parser, left: BoxedExpression) -> BoxedEx
             token,
             left: left?,
             index: index?,
+            optional,
         })));
     }
 
@@ -317,12 +351,38 @@ pub fn parse_index_expressions(p: &mut Parser, left: BoxedExpression) -> BoxedEx
         left: left?,
         left_index: index?,
         right_index: right?,
+        optional,
     })))
 }
 
-fn parse_fn_literal_parameters(p: &mut Parser) -> Option<Vec<Identifier>> {
+/// Parses a field access such as `obj.field`, which is sugar for indexing with a string, so it results in
+/// the same expression as `obj["field"]`.
+pub fn parse_dot_expression(p: &mut Parser, left: BoxedExpression) -> BoxedExpression {
+    let token = p.current_token.clone(); // .
+    if !p.expect_peek(TokenType::Ident) {
+        return None;
+    }
+
+    let index = StringLiteral {
+        token: Token {
+            token_type: TokenType::String,
+            ..p.current_token.clone()
+        },
+    };
+
+    Some(Box::new(AllExpressions::IndexExpression(IndexExpression {
+        token,
+        left: left?,
+        index: Box::new(AllExpressions::StringLiteral(index)),
+        optional: false,
+    })))
+}
+
+/// Parses a comma separated list of identifiers, such as function parameters, up to the given closing
+/// token. The closing token is left as the peek token.
+pub fn parse_identifier_list(p: &mut Parser, end: &TokenType) -> Option<Vec<Identifier>> {
     let mut parameters = Vec::new();
-    while !p.peek_token_is(&TokenType::Rparen) {
+    while !p.peek_token_is(end) {
         if !p.expect_peek(TokenType::Ident) {
             return None;
         }
@@ -332,7 +392,7 @@ fn parse_fn_literal_parameters(p: &mut Parser) -> Option<Vec<Identifier>> {
         };
         parameters.push(param);
 
-        if p.peek_token_is(&TokenType::Rparen) {
+        if p.peek_token_is(end) {
             break;
         }
 
@@ -348,8 +408,16 @@ fn parse_comma_sep_arguments(p: &mut Parser, end: &TokenType) -> Option<Vec<AllE
 
     while !p.peek_token_is(end) {
         p.next_token(); // consumes ( OR ,
-        let arg = p.parse_expression(Precedence::Lowest)?;
-        args.push(*arg);
+
+        if p.current_token_is(&TokenType::Spread) {
+            let token = p.current_token.clone();
+            p.next_token();
+            let value = p.parse_expression(Precedence::Lowest)?;
+            args.push(AllExpressions::Spread(SpreadExpression { token, value }));
+        } else {
+            let arg = p.parse_expression(Precedence::Lowest)?;
+            args.push(*arg);
+        }
 
         if p.peek_token_is(end) {
             break;
@@ -371,6 +439,9 @@ pub fn parse_block_statement(p: &mut Parser) -> BlockStatement {
 
     p.next_token();
 
+    // newlines terminate the statements of the block, even if the block is nested inside parentheses
+    let nesting = std::mem::replace(&mut p.nesting, 0);
+
     while !p.current_token_is(&TokenType::Rbrace) && !p.current_token_is(&TokenType::Eof) {
         let stmt = p.parse_statement();
         if let Some(v) = stmt {
@@ -379,25 +450,114 @@ pub fn parse_block_statement(p: &mut Parser) -> BlockStatement {
         p.next_token();
     }
 
+    p.nesting = nesting;
     block
 }
 
+/// Parses an expression starting with `{`, which is either a hash literal or a block expression.
+/// `{}` is always an empty hash literal.
+pub fn parse_brace_expression(p: &mut Parser) -> BoxedExpression {
+    if p.hash_literal_ahead() {
+        return parse_hash_literal(p);
+    }
+
+    let token = p.current_token.clone();
+    let body = parse_block_statement(p);
+
+    Some(Box::new(AllExpressions::Block(BlockExpression {
+        token,
+        body,
+    })))
+}
+
+/// Parses a match expression, such as `match value { [a, b] => a + b, n => n }`. The arms are
+/// separated by commas, and a trailing comma is allowed.
+pub fn parse_match_expression(p: &mut Parser) -> BoxedExpression {
+    let token = p.current_token.clone(); // match
+    p.next_token();
+
+    let value = p.parse_expression(Precedence::Lowest)?;
+    if !p.expect_peek(TokenType::Lbrace) {
+        return None;
+    }
+
+    let arms = p.nested(parse_match_arms)?;
+    p.next_token(); // consume }
+
+    Some(Box::new(AllExpressions::Match(MatchExpression {
+        token,
+        value,
+        arms,
+    })))
+}
+
+/// Parses the arms of a match expression up to the closing `}`, which is left as the peek token
+fn parse_match_arms(p: &mut Parser) -> Option<Vec<MatchArm>> {
+    let mut arms = Vec::new();
+
+    while !p.peek_token_is(&TokenType::Rbrace) {
+        p.next_token();
+        let pattern = parse_match_pattern(p)?;
+
+        if !p.expect_peek(TokenType::FatArrow) {
+            return None;
+        }
+        p.next_token();
+
+        let body = p.parse_expression(Precedence::Lowest)?;
+        arms.push(MatchArm { pattern, body });
+
+        if p.peek_token_is(&TokenType::Rbrace) {
+            break;
+        }
+        if !p.expect_peek(TokenType::Comma) {
+            return None;
+        }
+    }
+
+    Some(arms)
+}
+
+/// Parses the pattern of a match arm. Like let statements, arrays and hash maps are destructured into
+/// names, and any other expression is matched by its value.
+fn parse_match_pattern(p: &mut Parser) -> Option<MatchPattern> {
+    let pattern = match p.current_token.token_type {
+        TokenType::Ident => MatchPattern::Binding(Identifier {
+            token: p.current_token.clone(),
+            value: p.current_token.literal.clone(),
+        }),
+        TokenType::Lbracket => {
+            let names = parse_identifier_list(p, &TokenType::Rbracket)?;
+            p.next_token(); // consume ]
+            MatchPattern::Array(names)
+        }
+        TokenType::Lbrace => {
+            let names = parse_identifier_list(p, &TokenType::Rbrace)?;
+            p.next_token(); // consume }
+            MatchPattern::HashMap(names)
+        }
+        _ => MatchPattern::Value(p.parse_expression(Precedence::Lowest)?),
+    };
+
+    Some(pattern)
+}
+
 pub fn parse_hash_literal(p: &mut Parser) -> BoxedExpression {
     let token = p.current_token.clone();
-    let mut pairs = HashMap::new();
+    let mut pairs = Vec::new();
 
     while !p.peek_token_is(&TokenType::Rbrace) {
         p.next_token();
-        let key = p.parse_expression(Precedence::Lowest)?;
+        let key = p.nested(|p| p.parse_expression(Precedence::Lowest))?;
 
         if !p.expect_peek(TokenType::Colon) {
             return None;
         }
         p.next_token();
 
-        let value = p.parse_expression(Precedence::Lowest)?;
+        let value = p.nested(|p| p.parse_expression(Precedence::Lowest))?;
 
-        pairs.insert(*key, *value);
+        pairs.push((*key, *value));
 
         if !p.peek_token_is(&TokenType::Rbrace) && !p.expect_peek(TokenType::Comma) {
             return None;
@@ -1,50 +1,78 @@
-use crate::ast::expressions::Identifier;
-use crate::ast::statements::{AllStatements, LetStatement, ReturnStatement, WhileStatement};
+use crate::ast::expressions::{AllExpressions, Identifier};
+use crate::ast::statements::{
+    AllStatements, BreakStatement, LetStatement, LetTarget, LoopStatement, ReturnStatement,
+    WhileStatement,
+};
 use crate::lexer::token::TokenType;
 
-use super::parse_expressions::parse_block_statement;
+use super::parse_expressions::{parse_block_statement, parse_identifier_list};
 use super::{program::Parser, Precedence};
 
 impl Parser {
-    /// The high level statement parser. Delegates the work to the relevant parsers
+    /// The high level statement parser. Delegates the work to the relevant parsers.
+    ///
+    /// Every statement can be terminated by an optional semicolon. When the semicolon is omitted,
+    /// the statement ends at the end of its line, unless the line break is inside parentheses or
+    /// brackets, which allows long expressions to be split across several lines.
     pub fn parse_statement(&mut self) -> Option<AllStatements> {
         use TokenType::*;
 
-        match self.current_token.token_type {
+        let stmt = match self.current_token.token_type {
             Let => self.parse_let_statement(),
             Return => self.parse_return_statement(),
             While => self.parse_while_statement(),
+            Loop => self.parse_loop_statement(),
+            Break => self.parse_break_statement(),
             _ => self.parse_expression_statement(),
+        };
+
+        if self.peek_token_is(&Semicolon) {
+            self.next_token();
         }
+
+        stmt
     }
 
     /// Parses `Let` statements
     fn parse_let_statement(&mut self) -> Option<AllStatements> {
         let token = self.current_token.clone();
 
-        if !self.expect_peek(TokenType::Ident) {
-            return None;
-        }
-
-        let identifier = Identifier {
-            token: self.current_token.clone(),
-            value: self.current_token.literal.clone(),
+        let target = if self.peek_token_is(&TokenType::Lbracket) {
+            self.next_token();
+            let names = parse_identifier_list(self, &TokenType::Rbracket)?;
+            self.next_token(); // consumes ]
+            LetTarget::Array(names)
+        } else if self.peek_token_is(&TokenType::Lbrace) {
+            self.next_token();
+            let names = parse_identifier_list(self, &TokenType::Rbrace)?;
+            self.next_token(); // consumes }
+            LetTarget::HashMap(names)
+        } else {
+            if !self.expect_peek(TokenType::Ident) {
+                return None;
+            }
+
+            LetTarget::Identifier(Identifier {
+                token: self.current_token.clone(),
+                value: self.current_token.literal.clone(),
+            })
         };
 
-        if !self.expect_peek(TokenType::Assign) {
-            return None;
-        }
+        // a plain name can be declared without a value, as in `let x;`, which binds it to null
+        let value = if matches!(target, LetTarget::Identifier(_)) && self.let_value_omitted() {
+            Box::new(AllExpressions::NullLiteral)
+        } else {
+            if !self.expect_peek(TokenType::Assign) {
+                return None;
+            }
 
-        self.next_token();
-        let value = self.parse_expression(Precedence::Lowest)?;
-
-        if self.peek_token_is(&TokenType::Semicolon) {
             self.next_token();
-        }
+            self.parse_expression(Precedence::Lowest)?
+        };
 
         let stmt = LetStatement {
             token,
-            name: identifier,
+            target,
             value,
         };
 
@@ -59,10 +87,6 @@ impl Parser {
 
         let return_value = self.parse_expression(Precedence::Lowest)?;
 
-        if self.peek_token_is(&TokenType::Semicolon) {
-            self.next_token();
-        }
-
         let stmt = ReturnStatement {
             token,
             return_value,
@@ -100,4 +124,34 @@ impl Parser {
 
         Some(AllStatements::While(stmt))
     }
+
+    /// Parses `Loop` statements
+    fn parse_loop_statement(&mut self) -> Option<AllStatements> {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenType::Lbrace) {
+            return None;
+        }
+
+        let body = parse_block_statement(self);
+
+        Some(AllStatements::Loop(LoopStatement { token, body }))
+    }
+
+    /// Parses `Break` statements, which may optionally be followed by a value
+    fn parse_break_statement(&mut self) -> Option<AllStatements> {
+        let token = self.current_token.clone();
+        let mut value = None;
+
+        let has_value = !self.peek_token_is(&TokenType::Semicolon)
+            && !self.peek_token_is(&TokenType::Rbrace)
+            && !self.newline_terminates_statement();
+
+        if has_value {
+            self.next_token();
+            value = Some(self.parse_expression(Precedence::Lowest)?);
+        }
+
+        Some(AllStatements::Break(BreakStatement { token, value }))
+    }
 }
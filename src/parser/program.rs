@@ -1,13 +1,14 @@
-use super::tracing::Tracer;
+use super::{errors::ParseError, tracing::Tracer, Precedence};
 use crate::ast::expressions::AllExpressions;
 use crate::ast::program::Program;
 use crate::lexer::token::{eof_token, Token, TokenType};
 use crate::lexer::Lexer;
 use crate::parser::parse_expressions::{
-    parse_array_literal, parse_boolean_expression, parse_call_expression, parse_function_literal,
-    parse_grouped_expression, parse_hash_literal, parse_identifier, parse_if_expression,
-    parse_index_expressions, parse_infix_expression, parse_integer_literal, parse_null_literal,
-    parse_prefix_expression, parse_string_literal,
+    parse_array_literal, parse_boolean_expression, parse_brace_expression, parse_call_expression,
+    parse_dot_expression, parse_function_literal, parse_grouped_expression, parse_identifier,
+    parse_if_expression, parse_index_expressions, parse_infix_expression, parse_integer_literal,
+    parse_match_expression, parse_null_literal, parse_prefix_expression, parse_string_literal,
+    parse_when_expression,
 };
 
 /// A type alias for the optional boxed expression type that is commonly used in parser functions
@@ -22,11 +23,15 @@ pub type InfixParseFn = dyn Fn(&mut Parser, BoxedExpression) -> BoxedExpression;
 /// It includes the information needed for parsing as well as parser results
 pub struct Parser {
     pub l: Lexer,
-    pub errors: Vec<String>,
+    pub(super) errors: Vec<ParseError>,
     pub tracer: Tracer,
 
     pub current_token: Token,
     pub peek_token: Token,
+
+    /// The number of parentheses and brackets enclosing the current expression. Newlines only
+    /// terminate statements when the expression is not nested.
+    pub nesting: usize,
 }
 
 impl Parser {
@@ -40,6 +45,7 @@ impl Parser {
             peek_token: eof_token(),
             tracer: Tracer::new(),
             errors: vec![],
+            nesting: 0,
         };
 
         // Read two tokens, so curToken and peekToken are both set
@@ -49,12 +55,24 @@ impl Parser {
         p
     }
 
+    /// Returns the errors found while parsing, each positioned at the token it was found at
+    pub fn parse_errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    /// Returns the messages of the errors found while parsing, without their positions
+    pub fn error_messages(&self) -> Vec<String> {
+        self.errors.iter().map(|e| e.message.clone()).collect()
+    }
+
     /// The main parser method, which iterates through the tokens and generates a list of AST statements
     /// which ships with the `Program`
     pub fn parse_program(&mut self) -> Program {
         let mut program = Program::new();
 
         while self.current_token.token_type != TokenType::Eof {
+            // recover from any parser errors which left the nesting unbalanced
+            self.nesting = 0;
             let stmt = self.parse_statement();
             if let Some(s) = stmt {
                 program.statements.push(s);
@@ -65,6 +83,36 @@ impl Parser {
         program
     }
 
+    /// Parses the input as exactly one expression, which is useful for tooling that works on
+    /// expressions rather than whole programs. A trailing semicolon is allowed.
+    ///
+    /// Returns the parser errors, if the input is not a single valid expression
+    pub fn parse_single_expression(input: &str) -> Result<AllExpressions, Vec<ParseError>> {
+        let mut p = Parser::new(Lexer::new(input));
+        let expr = p.parse_expression(Precedence::Lowest);
+
+        if p.peek_token_is(&TokenType::Semicolon) {
+            p.next_token();
+        }
+
+        if !p.peek_token_is(&TokenType::Eof) {
+            let msg = format!(
+                "expected a single expression, got {:?} after the expression",
+                p.peek_token.token_type
+            );
+            p.errors.push(ParseError::at(msg, &p.peek_token));
+        }
+
+        if !p.errors.is_empty() {
+            return Err(p.errors);
+        }
+
+        expr.map(|e| *e).ok_or_else(|| {
+            let msg = "expected an expression".to_string();
+            vec![ParseError::at(msg, &p.current_token)]
+        })
+    }
+
     /// Returns the corresponding prefix parse function
     pub fn prefix_parse_function(token_type: &TokenType) -> Option<Box<PrefixParseFn>> {
         use TokenType::*;
@@ -79,8 +127,10 @@ impl Parser {
             If => Some(Box::new(parse_if_expression)),
             Function => Some(Box::new(parse_function_literal)),
             Lbracket => Some(Box::new(parse_array_literal)),
-            Lbrace => Some(Box::new(parse_hash_literal)),
+            Lbrace => Some(Box::new(parse_brace_expression)),
             Null => Some(Box::new(parse_null_literal)),
+            Match => Some(Box::new(parse_match_expression)),
+            When => Some(Box::new(parse_when_expression)),
             _ => None,
         }
     }
@@ -90,11 +140,11 @@ impl Parser {
         use TokenType::*;
 
         match token_type {
-            Plus | Minus | Asterisk | Slash | Eq | NotEq | Lt | Gt => {
-                Some(Box::new(parse_infix_expression))
-            }
+            Plus | Minus | Asterisk | Slash | Eq | NotEq | Lt | Gt | LtEq | GtEq | In
+            | NullCoalesce => Some(Box::new(parse_infix_expression)),
             Lparen => Some(Box::new(parse_call_expression)),
-            Lbracket => Some(Box::new(parse_index_expressions)),
+            Lbracket | OptionalLbracket => Some(Box::new(parse_index_expressions)),
+            Dot => Some(Box::new(parse_dot_expression)),
             _ => None,
         }
     }
@@ -107,7 +157,7 @@ mod tests {
 
     use super::test_helpers::*;
     use crate::ast::expressions::AllExpressions;
-    use crate::ast::statements::AllStatements;
+    use crate::ast::statements::{AllStatements, LetTarget};
     use crate::lexer::keywords;
 
     #[test]
@@ -128,12 +178,78 @@ mod tests {
                 panic!("{}", EXPECTED_LET);    
             };
             assert_eq!(let_stmt.token.literal, keywords::LET);
-            assert_eq!(let_stmt.name.value, tc.1);
-            assert_eq!(let_stmt.name.token.literal, tc.1);
+            let LetTarget::Identifier(name) = let_stmt.target else {
+                panic!("{}", EXPECTED_IDENT);
+            };
+            assert_eq!(name.value, tc.1);
+            assert_eq!(name.token.literal, tc.1);
             helper_test_literal(tc.2, *let_stmt.value);
         }
     }
 
+    #[test]
+    fn test_let_without_value() {
+        use super::Parser;
+        use crate::lexer::Lexer;
+
+        let test_cases = [
+            ("let x;", "let x = null;\n"),
+            ("let x", "let x = null;\n"),
+            ("let x\nx = 5", "let x = null;\nx = 5\n"),
+            ("let x; let y = 1;", "let x = null;\nlet y = 1;\n"),
+        ];
+        for tc in test_cases {
+            let mut program = helper_prepare_parser(tc.0);
+            assert_eq!(program.to_string(), tc.1);
+            let AllStatements::Let(let_stmt) = program.statements.remove(0) else {
+                panic!("{}", EXPECTED_LET);
+            };
+            assert!(matches!(*let_stmt.value, AllExpressions::NullLiteral));
+        }
+
+        // destructuring still needs a value to take apart
+        let mut p = Parser::new(Lexer::new("let [a, b];"));
+        p.parse_program();
+        assert_eq!(
+            p.errors[0].message,
+            "expected next token to be Assign, got Semicolon instead"
+        );
+    }
+
+    #[test]
+    fn test_let_destructuring() {
+        use Literal::*;
+
+        let mut program = helper_prepare_parser("let [a, b, c] = f(1);");
+        assert_eq!(program.statements.len(), 1);
+        let AllStatements::Let(let_stmt) = program.statements.remove(0) else {
+            panic!("{}", EXPECTED_LET);
+        };
+        let LetTarget::Array(names) = let_stmt.target else {
+            panic!("expected an array destructuring target");
+        };
+        let names = names.iter().map(|v| v.value.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["a", "b", "c"]);
+        let AllExpressions::CallExpression(call) = *let_stmt.value else {
+            panic!("expected a call expression");
+        };
+        helper_test_literal(Ident("f"), *call.function);
+
+        let program = helper_prepare_parser("let [x] = arr\nlet [] = arr");
+        assert_eq!(program.to_string(), "let [x] = arr;\nlet [] = arr;\n");
+
+        let mut program = helper_prepare_parser("let {name, age} = person");
+        let AllStatements::Let(let_stmt) = program.statements.remove(0) else {
+            panic!("{}", EXPECTED_LET);
+        };
+        assert_eq!(let_stmt.to_string(), "let {name, age} = person;");
+        let LetTarget::HashMap(names) = let_stmt.target else {
+            panic!("expected a hash map destructuring target");
+        };
+        let names = names.iter().map(|v| v.value.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["name", "age"]);
+    }
+
     #[test]
     fn test_return_statements() {
         use Literal::*;
@@ -185,6 +301,131 @@ mod tests {
         assert_eq!(stmt.body.statements.len(), 1);
     }
 
+    #[test]
+    fn test_newline_terminated_statements() {
+        let test_cases = [
+            (
+                "let x = 5\nlet y = x\nreturn x + y",
+                "let x = 5;\nlet y = x;\nreturn x + y;",
+            ),
+            // a line starting with a parenthesis or a bracket starts a new statement
+            ("let a = b\n(c)", "let a = b;\n(c);"),
+            ("arr\n[1, 2]", "arr;\n[1, 2];"),
+            // expressions inside parentheses and brackets can span multiple lines
+            ("add(\n1,\n2\n)\nx", "add(\n1,\n2\n);\nx;"),
+            ("let a = [1,\n2]\n(1\n+ 2)", "let a = [1,\n2];\n(1\n+ 2);"),
+            ("let a = 1 +\n2\na", "let a = 1 +\n2;\na;"),
+            // a token following a multi-line string on its closing line continues the statement
+            (
+                "let s = \"a\nb\" + \"c\"\nx",
+                "let s = \"a\nb\" + \"c\";\nx;",
+            ),
+            // newlines terminate statements inside blocks, even when nested in a call
+            (
+                "map(arr, fn(x) {\nlet y = x\ny * 2\n})",
+                "map(arr, fn(x) {\nlet y = x;\ny * 2;\n});",
+            ),
+            (
+                "loop {\nbreak\nx\n}\nwhile (true) { x }\ny",
+                "loop {\nbreak;\nx;\n};\nwhile (true) { x; };\ny;",
+            ),
+        ];
+
+        for tc in test_cases {
+            let program = helper_prepare_parser(tc.0);
+            let expected = helper_prepare_parser(tc.1);
+            assert_eq!(
+                program.to_string(),
+                expected.to_string(),
+                "input: {:?}",
+                tc.0
+            );
+            assert!(
+                program.statements == expected.statements,
+                "input: {:?}",
+                tc.0
+            );
+        }
+
+        // a line starting with an infix operator continues the statement
+        let continued_cases = [
+            ("let y = x\n + 1;", "let y = x + 1;"),
+            ("let y = x\n  - 1\nprint(y)", "let y = x - 1;\nprint(y);"),
+            ("let y = 1\n * 3", "let y = 1 * 3;"),
+            ("let y = 1\n == 3", "let y = 1 == 3;"),
+        ];
+
+        for tc in continued_cases {
+            let program = helper_prepare_parser(tc.0);
+            let expected = helper_prepare_parser(tc.1);
+            assert_eq!(
+                program.to_string(),
+                expected.to_string(),
+                "input: {:?}",
+                tc.0
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_single_expression() {
+        use super::Parser;
+        use Literal::*;
+
+        let expr = Parser::parse_single_expression("1 + 2 * 3").expect(EXPECTED_EXPRESSION);
+        let AllExpressions::InfixExpression(infix_expr) = expr else {
+            panic!("{}", EXPECTED_INFIX);
+        };
+        helper_test_literal(Int(1), *infix_expr.left.expect(EXPECTED_LEFT));
+        assert_eq!(infix_expr.operator, "+");
+        let right = *infix_expr.right.expect(EXPECTED_RIGHT);
+        helper_test_infix_expression(right, Int(2), "*", Int(3));
+
+        let expr = Parser::parse_single_expression("x;").expect(EXPECTED_EXPRESSION);
+        helper_test_literal(Ident("x"), expr);
+
+        let Err(errors) = Parser::parse_single_expression("1 + 2; 3") else {
+            panic!("expected a parser error");
+        };
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0]
+            .message
+            .starts_with("expected a single expression"));
+
+        assert!(Parser::parse_single_expression("").is_err());
+        assert!(Parser::parse_single_expression("let x = 5;").is_err());
+    }
+
+    #[test]
+    fn test_loop_and_break_statements() {
+        use Literal::*;
+        let input = "
+            loop {
+                break;
+                break x + 1;
+            }
+            ";
+        let mut program = helper_prepare_parser(input);
+        assert_eq!(program.statements.len(), 1);
+
+        let AllStatements::Loop(mut stmt) = program.statements.remove(0) else {
+            panic!("{}", EXPECTED_LOOP);
+        };
+        assert_eq!(stmt.token.literal, keywords::LOOP);
+        assert_eq!(stmt.body.statements.len(), 2);
+
+        let AllStatements::Break(break_stmt) = stmt.body.statements.remove(0) else {
+            panic!("{}", EXPECTED_BREAK);
+        };
+        assert!(break_stmt.value.is_none());
+
+        let AllStatements::Break(break_stmt) = stmt.body.statements.remove(0) else {
+            panic!("{}", EXPECTED_BREAK);
+        };
+        let value = break_stmt.value.expect(EXPECTED_EXPRESSION);
+        helper_test_infix_expression(*value, Ident("x"), "+", Int(1));
+    }
+
     #[test]
     fn test_parse_assignment_expressions() {
         let input = "x = 10;";
@@ -261,6 +502,8 @@ mod tests {
             ("5 / 5;", Int(5_i64), "/", Int(5_i64)),
             ("5 > 5;", Int(5_i64), ">", Int(5_i64)),
             ("5 < 5;", Int(5_i64), "<", Int(5_i64)),
+            ("5 >= 5;", Int(5_i64), ">=", Int(5_i64)),
+            ("5 <= 5;", Int(5_i64), "<=", Int(5_i64)),
             ("5 == 5;", Int(5_i64), "==", Int(5_i64)),
             ("5 != 5;", Int(5_i64), "!=", Int(5_i64)),
             ("true == true", Bool(true), "==", Bool(true)),
@@ -294,6 +537,13 @@ mod tests {
             ("3 + 4; -5 * 5", "(3 + 4)\n((-5) * 5)\n"),
             ("5 > 4 == 3 < 4", "((5 > 4) == (3 < 4))\n"),
             ("5 < 4 != 3 > 4", "((5 < 4) != (3 > 4))\n"),
+            ("5 <= 4 == 3 >= 4", "((5 <= 4) == (3 >= 4))\n"),
+            ("a + 1 in b", "((a + 1) in b)\n"),
+            ("a ?? b == c", "(a ?? (b == c))\n"),
+            ("a?[1]?[b][0:2] ?? c", "((((a?[1])?[b])[0:2]) ?? c)\n"),
+            ("-a?[1:2]", "(-(a?[1:2]))\n"),
+            ("a[1] ?? b ?? c + 1", "(((a[1]) ?? b) ?? (c + 1))\n"),
+            ("!(a in b) == c in d", "(((!(a in b)) == c) in d)\n"),
             (
                 "3 + 4 * 5 == 3 * 1 + 4 * 5",
                 "((3 + (4 * 5)) == ((3 * 1) + (4 * 5)))\n",
@@ -336,6 +586,108 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_chained_comparison_errors() {
+        use super::Parser;
+        use crate::lexer::Lexer;
+
+        let test_cases = [
+            ("1 < 2 < 3", "<", "<"),
+            ("a >= b + 1 > c", ">=", ">"),
+            ("let x = 1 <= 2 >= 3;", "<=", ">="),
+        ];
+
+        for tc in test_cases {
+            let mut p = Parser::new(Lexer::new(tc.0));
+            p.parse_program();
+            assert_eq!(
+                p.errors[0].message,
+                format!(
+                    "chained comparisons are not supported, found {} followed by {}; compare each pair separately",
+                    tc.1, tc.2
+                )
+            );
+        }
+
+        // grouping and mixing with equality operators is still allowed
+        for input in ["(1 < 2) == (3 > 2)", "1 < 2 == true", "f(1 < 2) < 3"] {
+            let mut p = Parser::new(Lexer::new(input));
+            p.parse_program();
+            helper_check_parser_errors(&p.errors);
+        }
+    }
+
+    #[test]
+    fn test_parse_error_positions() {
+        use super::Parser;
+        use crate::lexer::Lexer;
+
+        let mut p = Parser::new(Lexer::new("let x = 5;\n  let = 10;"));
+        p.parse_program();
+        let error = &p.parse_errors()[0];
+        assert_eq!(
+            error.message,
+            "expected next token to be Ident, got Assign instead"
+        );
+        assert_eq!((error.line, error.column), (2, 7));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 7: expected next token to be Ident, got Assign instead"
+        );
+        assert_eq!(p.error_messages()[0], error.message);
+
+        let test_cases = [("1 +\n)", 2, 1), ("1 < 2 < 3", 1, 7), ("f(1, 2", 1, 7)];
+        for tc in test_cases {
+            let mut p = Parser::new(Lexer::new(tc.0));
+            p.parse_program();
+            let error = &p.parse_errors()[0];
+            assert_eq!((error.line, error.column), (tc.1, tc.2), "input: {}", tc.0);
+        }
+
+        let Err(errors) = Parser::parse_single_expression("1 2") else {
+            panic!("expected an error for trailing tokens");
+        };
+        assert_eq!((errors[0].line, errors[0].column), (1, 3));
+    }
+
+    #[test]
+    fn test_too_large_integer_literal() {
+        use super::Parser;
+        use crate::lexer::Lexer;
+
+        let mut p = Parser::new(Lexer::new("let x = 99999999999999999999;"));
+        p.parse_program();
+        assert_eq!(
+            p.error_messages(),
+            ["integer literal 99999999999999999999 is too large for a 64-bit integer"]
+        );
+        let error = &p.parse_errors()[0];
+        assert_eq!((error.line, error.column), (1, 9));
+
+        helper_prepare_parser("9223372036854775807");
+    }
+
+    #[test]
+    fn test_parse_spread_expressions() {
+        let tests = [
+            ("add(...xs)", "add(...xs)\n"),
+            ("[0, ...xs, 9]", "[0, ...xs, 9]\n"),
+            ("f(1, ...g(x) + [2])", "f(1, ...(g(x) + [2]))\n"),
+        ];
+
+        for tc in tests {
+            let program = helper_prepare_parser(tc.0);
+            assert_eq!(program.to_string(), tc.1);
+        }
+
+        let mut p = super::Parser::new(crate::lexer::Lexer::new("let x = ...xs;"));
+        p.parse_program();
+        assert_eq!(
+            p.errors[0].message,
+            "no prefix parse function for Spread found"
+        );
+    }
+
     #[test]
     fn test_if_expression() {
         use Literal::Ident;
@@ -359,6 +711,26 @@ mod tests {
         assert!(if_expr.alternative.is_none());
     }
 
+    #[test]
+    fn test_when_expression() {
+        use Literal::{Ident, Int};
+
+        let mut program = helper_prepare_parser("when (x > 1) { x * 2 }");
+        assert_eq!(program.statements.len(), 1);
+
+        // a when expression is desugared into an if expression without an alternative
+        let mut if_expr = match helper_get_expression(program.statements.remove(0)) {
+            AllExpressions::IfExpression(v) => v,
+            _ => panic!("{}", EXPECTED_IF),
+        };
+        assert_eq!(if_expr.token.literal, "when");
+        helper_test_infix_expression(*if_expr.condition, Ident("x"), ">", Int(1));
+
+        let consequence = helper_get_expression(if_expr.consequence.statements.remove(0));
+        helper_test_infix_expression(consequence, Ident("x"), "*", Int(2));
+        assert!(if_expr.alternative.is_none());
+    }
+
     #[test]
     fn test_if_else_expression() {
         use Literal::Ident;
@@ -509,6 +881,39 @@ mod tests {
         helper_test_infix_expression(*expr.index, Int(1), "+", Int(1));
     }
 
+    #[test]
+    fn test_parse_dot_expressions() {
+        use super::Parser;
+        use crate::lexer::Lexer;
+
+        let test_cases = [
+            ("obj.x", r#"obj["x"]"#),
+            ("a.b.c", r#"a["b"]["c"]"#),
+            ("obj.f(1, 2)", r#"obj["f"](1, 2)"#),
+            ("-obj.x * 2", r#"-obj["x"] * 2"#),
+            ("f().x[0]", r#"f()["x"][0]"#),
+        ];
+        for tc in test_cases {
+            let dotted = helper_get_expression(helper_prepare_parser(tc.0).statements.remove(0));
+            let indexed = helper_get_expression(helper_prepare_parser(tc.1).statements.remove(0));
+            assert_eq!(dotted.to_string(), indexed.to_string(), "input: {}", tc.0);
+        }
+
+        let mut program = helper_prepare_parser("obj.x");
+        let AllExpressions::IndexExpression(expr) = helper_get_expression(program.statements.remove(0)) else {
+             panic!("{}", EXPECTED_INDEX_EXPRESSION);
+        };
+        helper_test_identifier(*expr.left, "obj");
+        assert!(matches!(*expr.index, AllExpressions::StringLiteral(s) if s.token.literal == "x"));
+
+        let mut p = Parser::new(Lexer::new("obj.1"));
+        p.parse_program();
+        assert_eq!(
+            p.error_messages(),
+            ["expected next token to be Ident, got Int instead"]
+        );
+    }
+
     #[test]
     fn test_parse_range_expressions() {
         use Literal::{Ident, Int};
@@ -525,6 +930,66 @@ mod tests {
         helper_test_infix_expression(*expr.right_index, Ident("x"), "-", Int(20));
     }
 
+    #[test]
+    fn test_parse_match_expressions() {
+        let test_cases = [
+            ("match x { n => n }", "match x { n => n }"),
+            (
+                "match [1, 2] { [a, b] => a + b, {k} => k, 1 + 1 => 2, }",
+                "match [1, 2] { [a, b] => (a + b), {k} => k, (1 + 1) => 2 }",
+            ),
+            (
+                "match f(x) {\n  \"a\" => 1,\n  _ => 0\n}",
+                "match f(x) { \"a\" => 1, _ => 0 }",
+            ),
+            ("match x {}", "match x {  }"),
+        ];
+        for tc in test_cases {
+            let expr = helper_get_expression(helper_prepare_parser(tc.0).statements.remove(0));
+            let AllExpressions::Match(_) = expr else {
+                panic!("expected a match expression for {}", tc.0);
+            };
+            assert_eq!(expr.to_string(), tc.1, "input: {}", tc.0);
+        }
+    }
+
+    #[test]
+    fn test_block_expression() {
+        let mut program = helper_prepare_parser("let y = { let t = 1; t + 1; };");
+        assert_eq!(program.statements.len(), 1);
+        let AllStatements::Let(let_stmt) = program.statements.remove(0) else {
+            panic!("{}", EXPECTED_LET);
+        };
+        let AllExpressions::Block(block) = *let_stmt.value else {
+            panic!("expected a block expression");
+        };
+        assert_eq!(block.body.statements.len(), 2);
+        assert_eq!(block.to_string(), "{\n    let t = 1;\n    (t + 1);\n}");
+
+        // a colon in the first entry makes it a hash literal, anything else is a block
+        let test_cases = [
+            ("{}", true),
+            (r#"{"a": 1}"#, true),
+            ("{a + 1: [1, 2][0:1]}", true),
+            ("{f(x, y): {1: 2}}", true),
+            ("{x}", false),
+            ("{ x; y }", false),
+            ("{ let a = {1: 2}; a }", false),
+            ("{ {1: 2} }", false),
+            ("{ f(1)[1:2] }", false),
+            ("{\n  a\n  b\n}", false),
+        ];
+        for tc in test_cases {
+            let expr = helper_get_expression(helper_prepare_parser(tc.0).statements.remove(0));
+            let is_hash = match expr {
+                AllExpressions::HashLiteral(_) => true,
+                AllExpressions::Block(_) => false,
+                _ => panic!("expected a hash literal or a block for {}", tc.0),
+            };
+            assert_eq!(is_hash, tc.1, "input: {}", tc.0);
+        }
+    }
+
     #[test]
     fn test_parse_hash_literal() {
         let input = r#"{"one": 1, "two": 2, "three": 3}"#;
@@ -538,12 +1003,12 @@ mod tests {
 
         let expected = HashMap::from([("one", 1), ("two", 2), ("three", 3)]);
 
-        for entry in expr.pairs.iter() {
-            let AllExpressions::StringLiteral(key) = entry.0 else {
+        for (key, value) in expr.pairs.iter() {
+            let AllExpressions::StringLiteral(key) = key else {
                 panic!("{}", EXPECTED_STRING);
             };
             let expected_int = expected.get(key.token.literal.as_str()).unwrap();
-            helper_test_integer_literal(entry.1, *expected_int);
+            helper_test_integer_literal(value, *expected_int);
         }
 
         // assert empty hash literal working correctly
@@ -560,7 +1025,7 @@ mod tests {
 /// Contains helper functions and constants useful for testing parsing
 #[cfg(test)]
 mod test_helpers {
-    use super::{Lexer, Parser};
+    use super::{Lexer, ParseError, Parser};
     use crate::ast::expressions::AllExpressions;
     use crate::ast::program::Program;
     use crate::ast::statements::AllStatements;
@@ -572,7 +1037,7 @@ mod test_helpers {
         Str(&'a str),
     }
 
-    pub fn helper_check_parser_errors(errors: &Vec<String>) {
+    pub fn helper_check_parser_errors(errors: &[ParseError]) {
         if errors.is_empty() {
             return;
         }
@@ -658,6 +1123,8 @@ mod test_helpers {
     pub const EXPECTED_LET: &str = "expected a let statement";
     pub const EXPECTED_RETURN: &str = "expected a return statement";
     pub const EXPECTED_WHILE: &str = "expected a while statement";
+    pub const EXPECTED_LOOP: &str = "expected a loop statement";
+    pub const EXPECTED_BREAK: &str = "expected a break statement";
     pub const EXPECTED_INTEGER: &str = "expected an integer literal";
     pub const EXPECTED_STRING: &str = "expected a string literal";
     pub const EXPECTED_BOOLEAN: &str = "expected a boolean expression";
@@ -1,24 +1,68 @@
 use crate::{
+    ast::program::Program,
     evaluator,
     lexer::Lexer,
-    object::{environment::Environment, Object},
-    parser::{Parser, TRACING_ENABLED},
+    object::{environment::Environment, AllObjects, Object},
+    parser::{ParseError, Parser, TRACING_ENABLED},
 };
 use clap::Parser as ClapParser;
 use std::{
     io::{self, BufRead, Write},
     rc::Rc,
+    time::Instant,
 };
 
 const PROMPT: &str = ">> ";
 
+/// The variable holding the result of the last program which evaluated to a useful value
+const LAST_RESULT: &str = "_";
+
+/// Describes how the execution of a program ended
+#[derive(Debug, PartialEq, Eq)]
+pub enum Termination {
+    /// The program ran to the end, which includes programs evaluating to a recoverable error
+    Completed,
+    /// The program was aborted by a fatal error, such as a `panic`
+    Aborted,
+    /// The program called `exit` with the given status code
+    Exited(i64),
+}
+
 /// The monkey programming language REPL (Read -> Evaluate -> Print -> Loop)
 #[derive(ClapParser)]
-#[clap(author, version, about, long_about = None)]
-struct Args {
+#[clap(author, version, about, long_about = None, trailing_var_arg = true)]
+pub struct Args {
     /// Enables tracing for parsing expressions
     #[clap(short, long, value_parser, default_value_t = false)]
-    tracing: bool,
+    pub tracing: bool,
+
+    /// Reports how long lexing, parsing and evaluating took to stderr
+    #[clap(long, value_parser, default_value_t = false)]
+    pub time: bool,
+
+    /// A script file to execute instead of starting the REPL, or `-` to read the script from stdin
+    #[clap(value_parser)]
+    pub file: Option<String>,
+
+    /// Arguments passed to the script, which it can read with `args()`. Everything after the script
+    /// is passed on, including arguments starting with `-`.
+    #[clap(value_parser, allow_hyphen_values = true)]
+    pub script_args: Vec<String>,
+}
+
+/// Runs the given function and, if timing is enabled, writes the wall-clock time it took to the
+/// report writer. The report is kept apart from the program output, which is left untouched.
+pub fn run_timed<T, W: Write>(
+    enabled: bool,
+    report: &mut W,
+    run: impl FnOnce() -> T,
+) -> io::Result<T> {
+    let start = Instant::now();
+    let result = run();
+    if enabled {
+        writeln!(report, "time: {:.3?}", start.elapsed())?;
+    }
+    Ok(result)
 }
 
 pub fn start_repl<T: BufRead, U: Write>(input: &mut T, output: &mut U) -> io::Result<()> {
@@ -44,7 +88,13 @@ pub fn start_repl<T: BufRead, U: Write>(input: &mut T, output: &mut U) -> io::Re
         }
 
         if !trimmed.is_empty() {
-            execute_program(&text, output, program_env.clone())?;
+            let termination = run_timed(args.time, &mut io::stderr(), || {
+                execute_with_format(&text, output, program_env.clone(), AllObjects::repr)
+            })??;
+            if let Termination::Exited(_) = termination {
+                writeln!(output, "bye")?;
+                break;
+            }
         }
 
         text.clear();
@@ -63,7 +113,7 @@ fn greet<U: Write>(output: &mut U) -> io::Result<()> {
     Ok(())
 }
 
-fn write_parser_errors<U: Write>(errors: &[String], output: &mut U) -> io::Result<()> {
+fn write_parser_errors<U: Write>(errors: &[ParseError], output: &mut U) -> io::Result<()> {
     writeln!(output, "{}", MONKEY_FACE)?;
     writeln!(output, "Woops! We ran into some monkey business here 🥴")?;
     writeln!(output, "parser Errors:")?;
@@ -87,27 +137,71 @@ const MONKEY_FACE: &str = r#"
            '-----'
 "#;
 
+/// Renders the result of a program for the output
+type ResultFormat = fn(&AllObjects) -> String;
+
 pub fn execute_program<U: Write>(
     text: &str,
     output: &mut U,
     program_env: Rc<Environment>,
-) -> io::Result<()> {
+) -> io::Result<Termination> {
+    execute_with_format(text, output, program_env, AllObjects::inspect)
+}
+
+/// Same as `execute_program`, but renders the result with the given format. The REPL uses `repr`,
+/// so that a string result can be told apart from other values.
+fn execute_with_format<U: Write>(
+    text: &str,
+    output: &mut U,
+    program_env: Rc<Environment>,
+    format: ResultFormat,
+) -> io::Result<Termination> {
     let l = Lexer::new(text);
     let mut p = Parser::new(l);
     let program = p.parse_program();
 
-    if !p.errors.is_empty() {
-        write_parser_errors(&p.errors, output)?;
-        return Ok(());
+    if !p.parse_errors().is_empty() {
+        write_parser_errors(p.parse_errors(), output)?;
+        return Ok(Termination::Completed);
     }
 
-    let evaluated = evaluator::eval(program.make_node(), program_env);
+    run_with_format(program, output, program_env, format)
+}
+
+/// Evaluates an already parsed program and writes its result to the output, which allows a program
+/// to be parsed once and run several times.
+///
+/// The result is also bound to `_` in the environment, so the next program can refer to it. Null
+/// results and errors are not bound, which keeps the previous useful result around.
+pub fn run_program<U: Write>(
+    program: Program,
+    output: &mut U,
+    program_env: Rc<Environment>,
+) -> io::Result<Termination> {
+    run_with_format(program, output, program_env, AllObjects::inspect)
+}
+
+fn run_with_format<U: Write>(
+    program: Program,
+    output: &mut U,
+    program_env: Rc<Environment>,
+    format: ResultFormat,
+) -> io::Result<Termination> {
+    let evaluated = evaluator::eval(program.make_node(), program_env.clone());
     match evaluated {
-        Some(e) if !e.is_null() => {
+        Some(AllObjects::ExitSignal(code)) => return Ok(Termination::Exited(code)),
+        Some(e @ AllObjects::FatalError(_)) => {
             writeln!(output, "{}", e.inspect())?;
+            return Ok(Termination::Aborted);
+        }
+        Some(e) if !e.is_null() => {
+            writeln!(output, "{}", format(&e))?;
+            if !e.is_error() {
+                program_env.set(LAST_RESULT.to_string(), e);
+            }
         }
         Some(_) | None => writeln!(output)?,
     }
 
-    Ok(())
+    Ok(Termination::Completed)
 }
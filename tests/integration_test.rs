@@ -1,4 +1,8 @@
-use interpreter_lib::read_file;
+use clap::Parser as ClapParser;
+use interpreter_lib::{
+    execute_program, read_file, read_file_with_env, read_program, run_program, run_timed, Args,
+    Environment, Lexer, Parser, Settings, Termination,
+};
 
 #[test]
 fn input_works() {
@@ -9,6 +13,7 @@ fn input_works() {
         ("array_map.mok", "[11, 21, 31, 41, 51]"),
         ("array_filter.mok", "[4, 10, 120, 90]"),
         ("array_reduce.mok", "38"),
+        ("shebang.mok", "20"),
     ];
     let base_path = "tests/testfiles";
 
@@ -27,3 +32,153 @@ fn input_works() {
         assert_eq!(tc.1, trimmed);
     }
 }
+
+#[test]
+fn panic_aborts_the_program() {
+    let mut output: Vec<u8> = Vec::new();
+    let termination = read_file("tests/testfiles/panic.mok".to_string(), &mut output).unwrap();
+
+    assert_eq!(termination, Termination::Aborted);
+    assert_eq!(
+        String::from_utf8(output).unwrap().trim(),
+        "Panic: negative input"
+    );
+}
+
+#[test]
+fn exit_surfaces_the_status_code() {
+    let mut output: Vec<u8> = Vec::new();
+    let input = "let x = 1; exit(x + 1); x;";
+    let termination = execute_program(input, &mut output, Environment::new()).unwrap();
+
+    assert_eq!(termination, Termination::Exited(2));
+    assert!(output.is_empty());
+}
+
+#[test]
+fn parsed_program_runs_many_times() {
+    let mut output: Vec<u8> = Vec::new();
+    let env = Environment::new();
+    execute_program("let count = 0; let total = 0;", &mut output, env.clone()).unwrap();
+
+    let input = "count = count + 1; total = total + count; total";
+    let mut p = Parser::new(Lexer::new(input));
+    let program = p.parse_program();
+    assert!(p.parse_errors().is_empty());
+
+    let mut output: Vec<u8> = Vec::new();
+    for _ in 0..3 {
+        let termination = run_program(program.clone(), &mut output, env.clone()).unwrap();
+        assert_eq!(termination, Termination::Completed);
+    }
+
+    assert_eq!(String::from_utf8(output).unwrap(), "1\n3\n6\n");
+}
+
+#[test]
+fn last_result_is_bound_to_underscore() {
+    let mut output: Vec<u8> = Vec::new();
+    let env = Environment::new();
+    for input in ["2 + 3", "_ * 2", "null", "_ + \"a\"", "_"] {
+        execute_program(input, &mut output, env.clone()).unwrap();
+    }
+
+    let output = String::from_utf8(output).unwrap();
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines[..3], ["5", "10", ""]);
+    assert!(lines[3].contains("type mismatch: INTEGER + STRING"));
+    assert_eq!(lines[4], "10");
+}
+
+#[test]
+fn time_flag_reports_without_changing_the_output() {
+    let file = "tests/testfiles/array_reduce.mok";
+    let args = Args::try_parse_from(["interpreter", file, "--time"]).unwrap();
+    assert!(args.time);
+    assert_eq!(args.file.as_deref(), Some(file));
+    assert!(!Args::try_parse_from(["interpreter"]).unwrap().time);
+
+    for enabled in [false, true] {
+        let mut output: Vec<u8> = Vec::new();
+        let mut report: Vec<u8> = Vec::new();
+        let termination = run_timed(enabled, &mut report, || {
+            read_file(args.file.clone().unwrap(), &mut output)
+        });
+
+        assert_eq!(termination.unwrap().unwrap(), Termination::Completed);
+        assert_eq!(String::from_utf8(output).unwrap().trim(), "38");
+
+        let report = String::from_utf8(report).unwrap();
+        assert_eq!(report.starts_with("time: "), enabled, "report: {}", report);
+    }
+}
+
+#[test]
+fn script_arguments_are_passed_to_the_program() {
+    let args =
+        Args::try_parse_from(["interpreter", "tests/testfiles/args.mok", "foo", "bar"]).unwrap();
+    assert_eq!(args.script_args, ["foo", "bar"]);
+
+    let env = Environment::new_with_settings(Settings {
+        program_args: args.script_args,
+        ..Default::default()
+    });
+    let mut output: Vec<u8> = Vec::new();
+    let termination = read_file_with_env(args.file.unwrap(), &mut output, env).unwrap();
+
+    assert_eq!(termination, Termination::Completed);
+    assert_eq!(String::from_utf8(output).unwrap().trim(), "bar foo");
+
+    let mut output: Vec<u8> = Vec::new();
+    execute_program("args()", &mut output, Environment::new()).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap().trim(), "[]");
+
+    // arguments starting with a hyphen belong to the script rather than the interpreter
+    let args = Args::try_parse_from([
+        "interpreter",
+        "--time",
+        "tests/testfiles/args.mok",
+        "-v",
+        "--name",
+        "x",
+    ])
+    .unwrap();
+    assert!(args.time);
+    assert_eq!(args.script_args, ["-v", "--name", "x"]);
+}
+
+#[test]
+fn program_is_read_from_a_reader() {
+    let mut input = "let double = fn(x) {\n  x * 2\n}\ndouble(5)".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    let termination = read_program(&mut input, &mut output).unwrap();
+
+    assert_eq!(termination, Termination::Completed);
+    assert_eq!(String::from_utf8(output).unwrap(), "10\n");
+
+    let mut input = "exit(3)".as_bytes();
+    let termination = read_program(&mut input, &mut Vec::new()).unwrap();
+    assert_eq!(termination, Termination::Exited(3));
+}
+
+#[test]
+fn parser_errors_are_reported_with_positions() {
+    let mut output: Vec<u8> = Vec::new();
+    let termination = execute_program("let x = 1;\nlet = 2;", &mut output, Environment::new());
+
+    assert_eq!(termination.unwrap(), Termination::Completed);
+    let output = String::from_utf8(output).unwrap();
+    assert!(
+        output.contains("line 2, column 5: expected next token to be Ident, got Assign instead")
+    );
+}
+
+#[test]
+fn only_the_first_line_can_be_a_shebang() {
+    let mut input = "#!/usr/bin/env interpreter\n#!not a shebang".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    read_program(&mut input, &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("line 2, column 1: no prefix parse function for Illegal found"));
+}